@@ -0,0 +1,124 @@
+use std::fmt;
+
+use blake2::{digest::consts::U32, Blake2b, Digest};
+
+type Blake2b256 = Blake2b<U32>;
+
+/// Mainnet network prefix + P2PK address type
+const MAINNET_P2PK_PREFIX: u8 = 0x01;
+/// Testnet network prefix + P2PK address type
+const TESTNET_P2PK_PREFIX: u8 = 0x11;
+/// Prefix byte + compressed public key + checksum
+const P2PK_LENGTH: usize = 1 + 33 + 4;
+const CHECKSUM_LENGTH: usize = 4;
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+#[derive(Debug, PartialEq)]
+pub enum AddressError {
+    Empty,
+    InvalidCharacter(char),
+    /// Not decodable as Base58, with the decoder's reason
+    InvalidBase58(String),
+    Testnet,
+    NotP2PK,
+    InvalidLength(usize),
+    InvalidPublicKey,
+    InvalidChecksum,
+}
+
+impl fmt::Display for AddressError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AddressError::Empty => write!(f, "Address is empty"),
+            AddressError::InvalidCharacter(c) => {
+                write!(f, "Invalid character '{}', not a Base58 string", c)
+            }
+            AddressError::InvalidBase58(e) => write!(f, "Not a valid Base58 string: {}", e),
+            AddressError::Testnet => write!(f, "Testnet address, a mainnet address is required"),
+            AddressError::NotP2PK => write!(f, "Not a P2PK address, it should start with '9'"),
+            AddressError::InvalidLength(len) => {
+                write!(f, "Invalid length: {} bytes, expected {}", len, P2PK_LENGTH)
+            }
+            AddressError::InvalidPublicKey => write!(f, "Invalid public key"),
+            AddressError::InvalidChecksum => write!(f, "Invalid checksum, check for typos"),
+        }
+    }
+}
+
+/// Validate an Ergo mainnet P2PK address (prefix, Base58 charset and checksum)
+pub fn validate(address: &str) -> Result<(), AddressError> {
+    let address = address.trim();
+
+    if address.is_empty() {
+        return Err(AddressError::Empty);
+    }
+
+    if let Some(c) = address.chars().find(|&c| !BASE58_ALPHABET.contains(c)) {
+        return Err(AddressError::InvalidCharacter(c));
+    }
+
+    let bytes = bs58::decode(address)
+        .into_vec()
+        .map_err(|e| AddressError::InvalidBase58(e.to_string()))?;
+
+    match bytes.first() {
+        Some(&MAINNET_P2PK_PREFIX) => {}
+        Some(&TESTNET_P2PK_PREFIX) => return Err(AddressError::Testnet),
+        _ => return Err(AddressError::NotP2PK),
+    }
+
+    if bytes.len() != P2PK_LENGTH {
+        return Err(AddressError::InvalidLength(bytes.len()));
+    }
+
+    // Compressed group element
+    if bytes[1] != 0x02 && bytes[1] != 0x03 {
+        return Err(AddressError::InvalidPublicKey);
+    }
+
+    let (body, checksum) = bytes.split_at(bytes.len() - CHECKSUM_LENGTH);
+    let hash = Blake2b256::digest(body);
+
+    if hash[..CHECKSUM_LENGTH] != *checksum {
+        return Err(AddressError::InvalidChecksum);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: &str = "9fRAWhdxEsTcdb8PhGNrZfwqa65zfkuYHAMmkQLcic1gdLSV5vA";
+
+    #[test]
+    fn mainnet_p2pk() {
+        assert_eq!(validate(ADDRESS), Ok(()));
+        assert_eq!(validate(&format!("  {}\n", ADDRESS)), Ok(()));
+    }
+
+    #[test]
+    fn invalid_addresses() {
+        assert_eq!(validate(" "), Err(AddressError::Empty));
+        // One character off
+        assert_eq!(
+            validate("9fRAWhdxEsxcdb8PhGNrZfwqa65zfkuYHAMmkQLcic1gdLSV5vA"),
+            Err(AddressError::InvalidChecksum)
+        );
+        // Same public key on testnet
+        assert_eq!(
+            validate("3WwWK6U2khXfCuoREuafbMBjpXJXMN6Y9M8Sj1wrUNfQBvaF4gBo"),
+            Err(AddressError::Testnet)
+        );
+        // Pay-to-script
+        assert_eq!(
+            validate("BvWEdX3DrzSKhpm2Afafz9H5J49x"),
+            Err(AddressError::NotP2PK)
+        );
+        assert_eq!(
+            validate("9fRAWhdxEsTcdb8PhGNrZfwqa65zfkuYHAMmkQLcic1gdLSV5v0"),
+            Err(AddressError::InvalidCharacter('0'))
+        );
+    }
+}
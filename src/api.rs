@@ -0,0 +1,423 @@
+use std::{collections::HashMap, fmt};
+
+use chrono::{DateTime, Utc};
+use reqwest::{Client, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize};
+
+/// The remote endpoints the stats are built from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Endpoint {
+    Pools,
+    Pool,
+    Blocks,
+    Miners,
+    PoolPerformance,
+    Miner,
+    MinerPerformance,
+    Payments,
+    /// Payment transactions looked up on the explorer
+    Transactions,
+    NetworkInfo,
+    Price,
+}
+
+impl fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Endpoint::Pools => "pools",
+            Endpoint::Pool => "pool",
+            Endpoint::Blocks => "blocks",
+            Endpoint::Miners => "top miners",
+            Endpoint::PoolPerformance => "pool performance",
+            Endpoint::Miner => "miner",
+            Endpoint::MinerPerformance => "miner performance",
+            Endpoint::Payments => "payments",
+            Endpoint::Transactions => "explorer transactions",
+            Endpoint::NetworkInfo => "network info",
+            Endpoint::Price => "price",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    /// The endpoint could not be reached or answered with an error status
+    Request {
+        endpoint: Endpoint,
+        source: reqwest::Error,
+    },
+    /// The response does not match the expected schema
+    Decode {
+        endpoint: Endpoint,
+        field: String,
+        source: serde_json::Error,
+    },
+    /// The response is valid but lacks the data we are looking for
+    Missing {
+        endpoint: Endpoint,
+        field: &'static str,
+    },
+}
+
+impl Error {
+    pub fn endpoint(&self) -> Endpoint {
+        match self {
+            Error::Request { endpoint, .. }
+            | Error::Decode { endpoint, .. }
+            | Error::Missing { endpoint, .. } => *endpoint,
+        }
+    }
+
+    /// The endpoint answered 404
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, Error::Request { source, .. } if source.status() == Some(StatusCode::NOT_FOUND))
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Request { endpoint, source } => {
+                write!(f, "{} API unreachable: {}", endpoint, source)
+            }
+            Error::Decode {
+                endpoint,
+                field,
+                source,
+            } => write!(f, "{} API: invalid `{}`: {}", endpoint, field, source),
+            Error::Missing { endpoint, field } => {
+                write!(f, "{} API: no data available for {}", endpoint, field)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// GET `url` and deserialize the JSON body, reporting the failing field on schema drift
+pub async fn fetch<T: DeserializeOwned>(
+    client: &Client,
+    endpoint: Endpoint,
+    url: &str,
+) -> Result<T, Error> {
+    let request_error = |source| Error::Request { endpoint, source };
+
+    let body = client
+        .get(url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(request_error)?
+        .text()
+        .await
+        .map_err(request_error)?;
+
+    decode(endpoint, &body)
+}
+
+/// Deserialize a JSON body of `endpoint`, with the path of the failing field
+fn decode<T: DeserializeOwned>(endpoint: Endpoint, body: &str) -> Result<T, Error> {
+    let deserializer = &mut serde_json::Deserializer::from_str(body);
+
+    serde_path_to_error::deserialize(deserializer).map_err(|e| Error::Decode {
+        endpoint,
+        field: e.path().to_string(),
+        source: e.into_inner(),
+    })
+}
+
+/// `GET /api/pools`
+#[derive(Debug, Clone, Deserialize)]
+pub struct PoolsResponse {
+    pub pools: Vec<PoolSummary>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolSummary {
+    pub id: String,
+    pub coin: Coin,
+    pub pool_stats: PoolStats,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Coin {
+    pub name: String,
+}
+
+/// `GET /api/pools/{pool}`
+#[derive(Debug, Clone, Deserialize)]
+pub struct PoolResponse {
+    pub pool: Pool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Pool {
+    pub pool_stats: PoolStats,
+    pub network_stats: NetworkStats,
+    pub total_blocks: u64,
+    #[serde(default)]
+    pub pool_effort: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolStats {
+    pub connected_miners: u64,
+    pub pool_hashrate: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkStats {
+    pub network_difficulty: f64,
+    pub block_height: u64,
+}
+
+/// `GET /api/pools/{pool}/performance`
+#[derive(Debug, Clone, Deserialize)]
+pub struct PoolPerformanceResponse {
+    pub stats: Vec<PoolPerformanceSample>,
+}
+
+/// Pool and network stats aggregated over an hour or a day
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolPerformanceSample {
+    pub pool_hashrate: f64,
+    pub network_hashrate: f64,
+    pub created: DateTime<Utc>,
+}
+
+/// `GET /api/pools/{pool}/blocks`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolBlock {
+    pub block_height: u64,
+    pub status: BlockStatus,
+    pub confirmation_progress: f64,
+    #[serde(default)]
+    pub effort: Option<f64>,
+    #[serde(default)]
+    pub reward: f64,
+    #[serde(default)]
+    pub miner: Option<String>,
+    pub created: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BlockStatus {
+    Pending,
+    Confirmed,
+    Orphaned,
+}
+
+/// An item of `GET /api/pools/{pool}/miners`, sorted by hashrate
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MinerSummary {
+    pub miner: String,
+    pub hashrate: f64,
+    pub shares_per_second: f64,
+}
+
+/// `GET /api/pools/{pool}/miners/{address}`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MinerResponse {
+    pub pending_shares: f64,
+    pub pending_balance: f64,
+    pub total_paid: f64,
+    #[serde(default)]
+    pub performance: Option<PerformanceSample>,
+    #[serde(default)]
+    pub performance_samples: Vec<PerformanceSample>,
+}
+
+/// A sample of `GET /api/pools/{pool}/miners/{address}/performance`
+#[derive(Debug, Clone, Deserialize)]
+pub struct PerformanceSample {
+    pub created: DateTime<Utc>,
+    #[serde(default)]
+    pub workers: HashMap<String, WorkerPerformance>,
+}
+
+impl PerformanceSample {
+    /// Sum of the hashrate of all workers, in H/s
+    pub fn hashrate(&self) -> f64 {
+        self.workers.values().map(|worker| worker.hashrate).sum()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkerPerformance {
+    pub hashrate: f64,
+    pub shares_per_second: f64,
+}
+
+/// `GET /api/pools/{pool}/miners/{address}/payments`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Payment {
+    pub amount: f64,
+    pub transaction_confirmation_data: String,
+    pub created: DateTime<Utc>,
+}
+
+/// `GET {explorer}/api/v1/transactions/{id}`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExplorerTransaction {
+    pub num_confirmations: u64,
+}
+
+/// `GET {explorer}/info`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkInfo {
+    pub hash_rate: f64,
+}
+
+/// `GET {explorer}/api/v1/networkState`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkState {
+    pub height: u64,
+}
+
+/// An item of the Spectrum `cmc/markets` list
+#[derive(Debug, Clone, Deserialize)]
+pub struct SpectrumMarket {
+    pub base_name: String,
+    pub quote_name: String,
+    pub last_price: f64,
+}
+
+/// `GET {coingecko}/simple/price?ids={id}&vs_currencies={currency}`, the
+/// prices by coin id and currency
+pub type CoinGeckoPrices = HashMap<String, HashMap<String, f64>>;
+
+/// `GET {explorer}/api/v1/boxes/unspent/byTokenId/{id}`
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExplorerBoxes {
+    pub items: Vec<ExplorerBox>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExplorerBox {
+    /// By register name, e.g. "R4"
+    pub additional_registers: HashMap<String, ExplorerRegister>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExplorerRegister {
+    pub rendered_value: String,
+}
+
+/// An item of `GET {node}/blockchain/box/unspent/byTokenId/{id}`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeBox {
+    /// Serialized values in hex by register name, e.g. "R4"
+    pub additional_registers: HashMap<String, String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POOL: &str = r#"{
+        "pool": {
+            "id": "ErgoSigmanauts",
+            "coin": { "type": "ERG", "name": "Ergo", "symbol": "ERG" },
+            "paymentProcessing": { "enabled": true, "minimumPayment": 0.5 },
+            "poolStats": {
+                "connectedMiners": 41,
+                "poolHashrate": 13824564018.0,
+                "sharesPerSecond": 2.1
+            },
+            "networkStats": {
+                "networkType": "Main",
+                "networkHashrate": 15013247619840.0,
+                "networkDifficulty": 1801589714305024.0,
+                "blockHeight": 1283245,
+                "connectedPeers": 60
+            },
+            "totalPaid": 8462.35,
+            "totalBlocks": 312,
+            "poolEffort": 0.4512
+        }
+    }"#;
+
+    const MINER: &str = r#"{
+        "pendingShares": 1024.5,
+        "pendingBalance": 0.85,
+        "totalPaid": 102.3,
+        "todayPaid": 0.0,
+        "performance": {
+            "created": "2026-10-17T07:00:00Z",
+            "workers": {
+                "rig1": { "hashrate": 110000000.0, "sharesPerSecond": 0.02 },
+                "": { "hashrate": 90000000.0, "sharesPerSecond": 0.01 }
+            }
+        },
+        "performanceSamples": [
+            {
+                "created": "2026-10-17T06:00:00Z",
+                "workers": { "rig1": { "hashrate": 100000000.0, "sharesPerSecond": 0.02 } }
+            }
+        ]
+    }"#;
+
+    const PAYMENTS: &str = r#"[
+        {
+            "coin": "ERG",
+            "address": "9fRAWhdxEsTcdb8PhGNrZfwqa65zfkuYHAMmkQLcic1gdLSV5vA",
+            "amount": 1.25,
+            "transactionConfirmationData": "d2f0b3c5e4a1",
+            "transactionInfoLink": "https://explorer.ergoplatform.com/en/transactions/d2f0b3c5e4a1",
+            "created": "2026-10-16T12:00:00Z"
+        }
+    ]"#;
+
+    #[test]
+    fn decodes_mining_core_responses() {
+        let pool: PoolResponse = decode(Endpoint::Pool, POOL).unwrap();
+        assert_eq!(pool.pool.pool_stats.connected_miners, 41);
+        assert_eq!(pool.pool.network_stats.block_height, 1283245);
+        assert_eq!(pool.pool.total_blocks, 312);
+
+        let miner: MinerResponse = decode(Endpoint::Miner, MINER).unwrap();
+        assert_eq!(miner.performance.unwrap().hashrate(), 200000000.0);
+        assert_eq!(miner.performance_samples.len(), 1);
+
+        let payments: Vec<Payment> = decode(Endpoint::Payments, PAYMENTS).unwrap();
+        assert_eq!(payments[0].amount, 1.25);
+        assert_eq!(payments[0].transaction_confirmation_data, "d2f0b3c5e4a1");
+    }
+
+    #[test]
+    fn decode_errors_report_the_failing_field() {
+        let body = POOL.replace("\"blockHeight\": 1283245", "\"blockHeight\": \"1283245\"");
+        let error = decode::<PoolResponse>(Endpoint::Pool, &body).unwrap_err();
+
+        assert!(matches!(
+            &error,
+            Error::Decode { endpoint: Endpoint::Pool, field, .. }
+                if field == "pool.networkStats.blockHeight"
+        ));
+        assert!(error
+            .to_string()
+            .starts_with("pool API: invalid `pool.networkStats.blockHeight`"));
+
+        let body = MINER.replace("\"pendingBalance\": 0.85,", "");
+        let error = decode::<MinerResponse>(Endpoint::Miner, &body).unwrap_err();
+        assert!(error.to_string().contains("missing field `pendingBalance`"));
+    }
+}
@@ -1,30 +1,106 @@
-use crate::{data::*, tui};
+use crate::{
+    address,
+    api::PoolSummary,
+    config::{Config, Watched},
+    data::*,
+    schedule::Source,
+    state::State,
+    status::Status,
+    tui,
+    views::{
+        blocks::BlocksView, dashboard::DashboardView, miners::MinersView, payments::PaymentsView,
+        settings::SettingsView, workers::WorkersView, Context, Tab, View,
+    },
+    worker::{self, Command, Update},
+};
+use chrono::TimeDelta;
 use crossterm::event::{self, poll, Event, KeyCode, KeyEvent, KeyEventKind};
-use ratatui::{prelude::*, widgets::*};
+use ratatui::{
+    prelude::*,
+    widgets::{block::Title, *},
+};
 use std::{io, time::Duration, vec};
 
+/// How often the screen is redrawn and the input polled
+const TICK_RATE: Duration = Duration::from_millis(250);
+
+/// Title on the right of the top line, next to the tabs
+const TITLE: &str = " Sigmanauts Mining Pool ";
+
+#[derive(Debug, Default, PartialEq)]
+enum InputMode {
+    #[default]
+    Normal,
+    EditingAddress,
+    SelectingPool,
+}
+
 #[derive(Debug, Default)]
 pub struct App {
+    config: Config,
     address: String,
+    input_mode: InputMode,
+    input: String,
+    input_error: Option<String>,
+    /// Pools of the Mining Core instance, `None` while loading
+    pools: Option<Vec<PoolSummary>>,
+    pool_list: ListState,
+    /// Requests for the fetch task, sent on the next tick
+    commands: Vec<Command>,
+    status: Status,
+    tab: Tab,
+    dashboard: DashboardView,
+    blocks: BlocksView,
+    payments: PaymentsView,
+    workers: WorkersView,
+    miners: MinersView,
+    settings: SettingsView,
     exit: bool,
 }
 
 impl App {
+    pub fn new(config: Config) -> App {
+        App {
+            address: config
+                .addresses
+                .first()
+                .map(|watched| watched.address.clone())
+                .unwrap_or_default(),
+            config,
+            ..Default::default()
+        }
+    }
+
     /// runs the application's main loop until the user quits
     pub fn run(&mut self, terminal: &mut tui::Tui) -> io::Result<()> {
-        let mut stats = Stats::default();
+        let runtime = tokio::runtime::Runtime::new()?;
+        let mut worker = worker::spawn(&runtime, &self.config, self.address.clone());
+        let mut stats = Stats::new(&self.config);
 
         while !self.exit {
-            match stats.get_data() {
-                Ok(_) => self.exit = false,
-                Err(_) => {
-                    println!("API UNREACHABLE!");
+            while let Ok(update) = worker.updates.try_recv() {
+                match update {
+                    Update::Stats(new_stats, endpoints) => {
+                        stats = *new_stats;
+                        self.status.record_success(&endpoints);
+                    }
+                    Update::Pools(pools) => {
+                        let selected = pools.iter().position(|pool| pool.id == self.config.pool_id);
+                        self.pool_list.select(selected.or(Some(0)));
+                        self.pools = Some(pools);
+                    }
+                    Update::Error(e) => self.status.record_error(&e),
+                    Update::Chain(event) => self.status.record_event(event),
                 }
             }
 
+            for command in self.commands.drain(..) {
+                let _ = worker.commands.send(command);
+            }
+
             terminal.draw(|frame| self.render_frame(frame, &stats))?;
 
-            if poll(Duration::from_secs(60))? {
+            if poll(TICK_RATE)? {
                 self.handle_events()?;
             }
         }
@@ -42,19 +118,45 @@ impl App {
         )
         .split(frame.size());
 
+        //The tabs are cut on narrow terminals, the title is kept
+        let top_layout = Layout::new(
+            Direction::Horizontal,
+            [
+                Constraint::Min(0),
+                Constraint::Length(TITLE.chars().count() as u16),
+            ],
+        )
+        .split(main_layout[0]);
+
         frame.render_widget(
             Block::new()
                 .borders(Borders::TOP)
-                .title(" Sigmanauts Mining Pool ")
-                .title_alignment(Alignment::Center)
+                .title(TITLE)
+                .title_alignment(Alignment::Right)
                 .title_style(Style::default().fg(Color::Green))
                 .border_style(Style::fg(Style::default().fg(Color::Green), Color::Green)),
             main_layout[0],
         );
 
+        frame.render_widget(
+            Tabs::new(
+                Tab::ALL
+                    .iter()
+                    .enumerate()
+                    .map(|(i, tab)| format!("{} {}", i + 1, tab.title())),
+            )
+            .select(self.tab.index())
+            .style(Style::default().green())
+            .highlight_style(Style::default().black().on_green())
+            .padding(" ", " ")
+            .divider(symbols::line::HORIZONTAL),
+            top_layout[0],
+        );
+
         frame.render_widget(
             Block::new()
                 .borders(Borders::TOP)
+                .title(Title::from(self.status_line()).alignment(Alignment::Left))
                 .title(" v0.0.1 ")
                 .title_alignment(Alignment::Right)
                 .title_style(Color::Green)
@@ -62,335 +164,144 @@ impl App {
             main_layout[2],
         );
 
-        let stats_layout = Layout::new(
-            Direction::Vertical,
-            [
-                Constraint::Percentage(33),
-                Constraint::Percentage(33),
-                Constraint::Percentage(33),
-            ],
-        )
-        .split(main_layout[1]);
-
-        frame.render_widget(
-            Block::bordered()
-                .title(" Network Stats ")
-                .border_style(Style::fg(Style::default().fg(Color::Green), Color::Green)),
-            stats_layout[0],
-        );
-        frame.render_widget(
-            Block::bordered()
-                .title(" Pool Stats ".green())
-                .border_style(Style::fg(Style::default().fg(Color::Green), Color::Green)),
-            stats_layout[1],
-        );
-        frame.render_widget(
-            Block::bordered()
-                .title(" Miner Stats ")
-                .border_style(Style::fg(Style::default().fg(Color::Green), Color::Green)),
-            stats_layout[2],
-        );
-
-        self.render(
-            frame,
-            stats_layout[0],
-            vec![
-                " Network Hashrate ",
-                " Network Difficulty ",
-                " Block Height ",
-            ],
-            vec![" Block Reward ", " Reward Reduction in ", " ERG Price "],
-            vec![
-                (stats
-                    .network
-                    .hashrate
-                    .back()
-                    .unwrap_or(&(0.0, 0.0))
-                    .1
-                    .to_string()
-                    + " Th/s")
-                    .as_str(),
-                (stats.network.difficulty.to_string() + " P").as_str(),
-                stats.network.height.to_string().as_str(),
-            ],
-            vec![
-                (stats.network.reward.to_string() + " Σ").as_str(),
-                stats.network.reward_reduction.to_string().as_str(),
-                (stats.network.price.to_string() + " SigUSD").as_str(),
-            ],
-            "Network Hashrate",
-            "Block",
-            "Th/s",
-            stats.network.hashrate.clone().into_iter().collect(),
-        );
-
-        self.render(
-            frame,
-            stats_layout[1],
-            vec![" Pool Hashrate ", " Connected Miners ", " Current Effort "],
-            vec![
-                " Block found every ",
-                " Blocks found ",
-                " Confirming block ",
-            ],
-            vec![
-                (stats
-                    .pool
-                    .hashrate
-                    .back()
-                    .unwrap_or(&(0.0, 0.0))
-                    .1
-                    .to_string()
-                    + " Gh/s")
-                    .as_str(),
-                stats.pool.connected_miners.to_string().as_str(),
-                (stats.pool.effort.to_string() + " %").as_str(),
-            ],
-            vec!["", stats.pool.total_blocks.to_string().as_str(), ""],
-            "Pool Hashrate",
-            "Block",
-            "Gh/s",
-            stats.pool.hashrate.clone().into_iter().collect(),
-        );
+        let no_miner = MinerStats::default();
+        let ctx = Context {
+            stats,
+            config: &self.config,
+            address: &self.address,
+            miner: stats.miner(&self.address).unwrap_or(&no_miner),
+            stale: self.is_stale(),
+        };
 
-        // Adding Progress Bar
-        let layout_1 = Layout::new(
-            Direction::Horizontal,
-            [Constraint::Percentage(50), Constraint::Percentage(50)],
-        )
-        .margin(1)
-        .split(stats_layout[1]);
+        let view: &mut dyn View = match self.tab {
+            Tab::Dashboard => &mut self.dashboard,
+            Tab::Blocks => &mut self.blocks,
+            Tab::Payments => &mut self.payments,
+            Tab::Workers => &mut self.workers,
+            Tab::Miners => &mut self.miners,
+            Tab::Settings => &mut self.settings,
+        };
+        view.render(frame, main_layout[1], &ctx);
 
-        // Split the area in 2 segments:
-        let stats_layout_progress = Layout::new(
-            Direction::Horizontal,
-            [Constraint::Percentage(50), Constraint::Percentage(50)],
-        )
-        .margin(1)
-        .split(layout_1[0]);
+        match self.input_mode {
+            InputMode::EditingAddress => self.render_address_popup(frame),
+            InputMode::SelectingPool => self.render_pool_popup(frame),
+            InputMode::Normal => {}
+        }
+    }
 
-        let stats_right = Layout::new(
-            Direction::Vertical,
-            [
-                Constraint::Percentage(33),
-                Constraint::Percentage(33),
-                Constraint::Percentage(33),
-            ],
-        )
-        .split(stats_layout_progress[1]);
+    fn render_address_popup(&self, frame: &mut Frame) {
+        let area = centered_rect(60, 7, frame.size());
 
-        frame.render_widget(
-            Gauge::default()
-                .block(Block::new().padding(Padding::proportional(1)))
-                .gauge_style(Style::default().fg(Color::LightGreen))
-                .percent(stats.pool.confirming_new_block as u16),
-            stats_right[2],
-        );
+        let block = Block::bordered()
+            .title(" Wallet Address ")
+            .title_alignment(Alignment::Center)
+            .title_bottom(Line::from(" Enter: confirm | Esc: cancel ").right_aligned())
+            .border_style(Style::default().fg(Color::Green));
 
-        self.render(
-            frame,
-            stats_layout[2],
-            vec![
-                " Current Hashrate ",
-                " Average 24h Hashrate ",
-                " Round Contribution ",
-            ],
-            vec![" Pending Shares ", " Pending Balance ", " Total Paid "],
-            vec![
-                (stats
-                    .miner
-                    .hashrate
-                    .back()
-                    .unwrap_or(&(0.0, 0.0))
-                    .1
-                    .to_string()
-                    + " Mh/s")
-                    .as_str(),
-                stats.miner.average_hashrate.to_string().as_str(),
-                stats.miner.round_contribution.to_string().as_str(),
-            ],
-            vec![
-                stats.miner.pending_shares.to_string().as_str(),
-                stats.miner.pending_balance.to_string().as_str(),
-                stats.miner.total_paid.to_string().as_str(),
-            ],
-            "Miner Hashrate",
-            "Time",
-            "Mh/s",
-            stats.miner.hashrate.clone().into_iter().collect(),
-        );
-    }
+        let error = match &self.input_error {
+            Some(error) => Line::from(error.as_str()).red(),
+            None => Line::from(""),
+        };
 
-    fn render_stats(
-        &self,
-        frame: &mut Frame,
-        area: std::rc::Rc<[Rect]>,
-        title: Vec<&str>,
-        value: Vec<&str>,
-    ) {
-        for i in 0..area.len() {
-            let block = Block::bordered()
-                .title(title[i])
-                .title_alignment(Alignment::Center)
-                .style(Style::default().green());
-
-            let paragraph = Paragraph::new(value[i])
-                .alignment(Alignment::Center)
-                .block(block)
-                .light_green();
-
-            frame.render_widget(paragraph, area[i]);
-        }
+        let paragraph = Paragraph::new(vec![
+            Line::from(""),
+            Line::from(self.input.as_str()).light_green(),
+            Line::from(""),
+            error,
+        ])
+        .alignment(Alignment::Center)
+        .block(block);
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(paragraph, area);
     }
 
-    fn render_chart<'a>(
-        &self,
-        name: &'static str,
-        x_axis_title: &'static str,
-        y_axis_title: &'static str,
-        style: Style,
-        data: &'a Vec<(f64, f64)>,
-    ) -> Chart<'a> {
-        // Create the datasets to fill the chart with
-        let datasets = vec![
-            // Line chart
-            Dataset::default()
-                .name(name)
-                .marker(symbols::Marker::Dot)
-                .graph_type(GraphType::Line)
-                .style(style)
-                .data(data),
-        ];
-
-        let min_value_x = data
-            .iter()
-            .map(|&(x, _)| x)
-            .min_by(|a, b| a.partial_cmp(b).unwrap())
-            .unwrap_or(0.0);
-
-        let max_value_x = data
-            .iter()
-            .map(|&(x, _)| x)
-            .max_by(|a, b| a.partial_cmp(b).unwrap())
-            .unwrap_or(0.0);
-
-        // Create the X axis and define its properties
-        let x_axis = Axis::default()
-            .title(x_axis_title.green())
-            .style(Style::default().green())
-            .bounds([min_value_x, max_value_x])
-            .labels(vec![
-                min_value_x.to_string().into(),
-                ((min_value_x + max_value_x) / 2.0)
-                    .round()
-                    .to_string()
-                    .into(),
-                max_value_x.to_string().into(),
-            ]);
-
-        let min_value_y = data
-            .iter()
-            .map(|&(_, y)| y)
-            .min_by(|a, b| a.partial_cmp(b).unwrap())
-            .unwrap_or(0.0);
+    fn render_pool_popup(&mut self, frame: &mut Frame) {
+        let area = centered_rect(60, 12, frame.size());
+
+        let block = Block::bordered()
+            .title(" Select Pool ")
+            .title_alignment(Alignment::Center)
+            .title_bottom(Line::from(" Enter: select | Esc: cancel ").right_aligned())
+            .border_style(Style::default().fg(Color::Green));
+
+        frame.render_widget(Clear, area);
+
+        let Some(pools) = &self.pools else {
+            frame.render_widget(
+                Paragraph::new("Loading pools...")
+                    .alignment(Alignment::Center)
+                    .light_green()
+                    .block(block),
+                area,
+            );
+            return;
+        };
 
-        let max_value_y = data
+        let items: Vec<ListItem> = pools
             .iter()
-            .map(|&(_, y)| y)
-            .max_by(|a, b| a.partial_cmp(b).unwrap())
-            .unwrap_or(0.0);
-
-        // Create the Y axis and define its properties
-        let y_axis = Axis::default()
-            .title(y_axis_title.green())
-            .style(Style::default().green())
-            .bounds([
-                min_value_y - (min_value_y * 0.1),
-                max_value_y + (max_value_y * 0.1),
-            ])
-            .labels(vec![
-                ((((min_value_y - (min_value_y * 0.1)) * 100.0).round()) / 100.0)
-                    .to_string()
-                    .into(),
-                (((((min_value_y + max_value_y) / 2.0) * 100.0).round()) / 100.0)
-                    .to_string()
-                    .into(),
-                ((((max_value_y + (max_value_y * 0.1)) * 100.0).round()) / 100.0)
-                    .to_string()
-                    .into(),
-            ]);
-
-        // Create the chart and link all the parts together
-        Chart::new(datasets)
-            .block(Block::new())
-            .x_axis(x_axis)
-            .y_axis(y_axis)
+            .map(|pool| {
+                let current = if pool.id == self.config.pool_id {
+                    "* "
+                } else {
+                    "  "
+                };
+                ListItem::new(format!(
+                    "{}{} - {} - {} miners",
+                    current, pool.id, pool.coin.name, pool.pool_stats.connected_miners
+                ))
+            })
+            .collect();
+
+        frame.render_stateful_widget(
+            List::new(items)
+                .light_green()
+                .highlight_style(Style::default().reversed())
+                .block(block),
+            area,
+            &mut self.pool_list,
+        );
     }
 
-    fn render(
-        &self,
-        frame: &mut Frame,
-        layout: Rect,
-        stats_title_left: Vec<&str>,
-        stats_title_right: Vec<&str>,
-        stats_value_left: Vec<&str>,
-        stats_value_right: Vec<&str>,
-        chart_name: &'static str,
-        chart_x_axis_title: &'static str,
-        chart_y_axis_title: &'static str,
-        chart_data: Vec<(f64, f64)>,
-    ) {
-        // Rendering Stats
-        let layout_1 = Layout::new(
-            Direction::Horizontal,
-            [Constraint::Percentage(50), Constraint::Percentage(50)],
-        )
-        .margin(1)
-        .split(layout);
+    /// last update time, chain event of the last hour and fetch errors, shown in the bottom bar
+    fn status_line(&self) -> Line<'_> {
+        let mut spans = match self.status.last_update() {
+            Some(last_update) => {
+                vec![format!(" Updated {} ", last_update.format("%H:%M:%S")).green()]
+            }
+            None => vec![" Waiting for data... ".green()],
+        };
 
-        // Split the area in 2 segments:
-        let stats_layout = Layout::new(
-            Direction::Horizontal,
-            [Constraint::Percentage(50), Constraint::Percentage(50)],
-        )
-        .margin(1)
-        .split(layout_1[0]);
+        if self.is_stale() && self.status.last_update().is_some() {
+            spans.push(" STALE ".black().on_yellow());
+        }
 
-        let stats_left = Layout::new(
-            Direction::Vertical,
-            [
-                Constraint::Percentage(33),
-                Constraint::Percentage(33),
-                Constraint::Percentage(33),
-            ],
-        )
-        .split(stats_layout[0]);
+        if let Some((time, event)) = self.status.recent_event(TimeDelta::hours(1)) {
+            spans.push(
+                format!(" {} {} ", time.format("%H:%M"), event)
+                    .black()
+                    .on_yellow(),
+            );
+        }
 
-        let stats_right = Layout::new(
-            Direction::Vertical,
-            [
-                Constraint::Percentage(33),
-                Constraint::Percentage(33),
-                Constraint::Percentage(33),
-            ],
-        )
-        .split(stats_layout[1]);
+        for error in self.status.errors() {
+            spans.push(format!(" {} ", error).red());
+        }
 
-        self.render_stats(frame, stats_left, stats_title_left, stats_value_left);
+        Line::from(spans)
+    }
 
-        self.render_stats(frame, stats_right, stats_title_right, stats_value_right);
+    /// the data is stale after two missed refreshes of the most frequent source
+    fn is_stale(&self) -> bool {
+        let interval = Source::ALL
+            .into_iter()
+            .map(|source| self.config.interval(source))
+            .min()
+            .unwrap_or(self.config.refresh_interval);
 
-        // Rendering Charts
-        frame.render_widget(
-            self.render_chart(
-                chart_name,
-                chart_x_axis_title,
-                chart_y_axis_title,
-                Style::default().white(),
-                &chart_data,
-            ),
-            layout_1[1],
-        );
+        self.status
+            .is_stale(TimeDelta::seconds(2 * interval as i64))
     }
 
     /// updates the application's state based on user input
@@ -407,13 +318,165 @@ impl App {
     }
 
     pub fn handle_key_event(&mut self, key_event: KeyEvent) {
-        match key_event.code {
-            KeyCode::Char('q') => self.exit(),
-            _ => {}
+        match self.input_mode {
+            InputMode::Normal => match key_event.code {
+                KeyCode::Char('q') => self.exit(),
+                KeyCode::Char('a') => {
+                    self.input = self.address.clone();
+                    self.input_error = None;
+                    self.input_mode = InputMode::EditingAddress;
+                }
+                KeyCode::Char('w') => self.show_next_address(),
+                KeyCode::Char('P') => {
+                    self.pools = None;
+                    self.input_mode = InputMode::SelectingPool;
+                    self.commands.push(Command::ListPools);
+                }
+                KeyCode::Char(c @ '1'..='9') => {
+                    if let Some(tab) = Tab::from_number(c) {
+                        self.tab = tab;
+                    }
+                }
+                KeyCode::Tab | KeyCode::Right => self.tab = self.tab.next(),
+                KeyCode::BackTab | KeyCode::Left => self.tab = self.tab.previous(),
+                _ => {
+                    if let Some(command) = self.current_view().handle_key_event(key_event) {
+                        self.commands.push(command);
+                    }
+                }
+            },
+            InputMode::EditingAddress => match key_event.code {
+                KeyCode::Enter => self.submit_address(),
+                KeyCode::Esc => self.input_mode = InputMode::Normal,
+                KeyCode::Backspace => {
+                    self.input.pop();
+                    self.input_error = None;
+                }
+                KeyCode::Char(c) => {
+                    self.input.push(c);
+                    self.input_error = None;
+                }
+                _ => {}
+            },
+            InputMode::SelectingPool => match key_event.code {
+                KeyCode::Enter => self.submit_pool(),
+                KeyCode::Esc => self.input_mode = InputMode::Normal,
+                KeyCode::Down | KeyCode::Char('j') => {
+                    let last = self
+                        .pools
+                        .as_ref()
+                        .map_or(0, |pools| pools.len().saturating_sub(1));
+                    let selected = self.pool_list.selected().map_or(0, |i| (i + 1).min(last));
+                    self.pool_list.select(Some(selected));
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    let selected = self.pool_list.selected().unwrap_or(0).saturating_sub(1);
+                    self.pool_list.select(Some(selected));
+                }
+                _ => {}
+            },
+        }
+    }
+
+    fn current_view(&mut self) -> &mut dyn View {
+        match self.tab {
+            Tab::Dashboard => &mut self.dashboard,
+            Tab::Blocks => &mut self.blocks,
+            Tab::Payments => &mut self.payments,
+            Tab::Workers => &mut self.workers,
+            Tab::Miners => &mut self.miners,
+            Tab::Settings => &mut self.settings,
+        }
+    }
+
+    /// shows the stats of the watched address following the current one
+    fn show_next_address(&mut self) {
+        let addresses = &self.config.addresses;
+        if addresses.is_empty() {
+            return;
         }
+
+        let next = addresses
+            .iter()
+            .position(|watched| watched.address == self.address)
+            .map_or(0, |i| (i + 1) % addresses.len());
+        self.address = addresses[next].address.clone();
+        self.commands
+            .push(Command::SetAddress(self.address.clone()));
+    }
+
+    /// validates the entered address and, if valid, adds it to the watchlist and shows it
+    fn submit_address(&mut self) {
+        match address::validate(&self.input) {
+            Ok(_) => {
+                self.address = self.input.trim().to_string();
+                if !self
+                    .config
+                    .addresses
+                    .iter()
+                    .any(|watched| watched.address == self.address)
+                {
+                    self.config.addresses.push(Watched::new(&self.address));
+                }
+                self.input_mode = InputMode::Normal;
+                self.commands
+                    .push(Command::SetAddress(self.address.clone()));
+            }
+            Err(e) => self.input_error = Some(e.to_string()),
+        }
+    }
+
+    /// switches to the selected pool and remembers it for the next start
+    fn submit_pool(&mut self) {
+        let Some(pool) = self
+            .pools
+            .as_ref()
+            .zip(self.pool_list.selected())
+            .and_then(|(pools, i)| pools.get(i))
+        else {
+            return;
+        };
+
+        self.input_mode = InputMode::Normal;
+
+        if pool.id == self.config.pool_id {
+            return;
+        }
+
+        self.config.pool_id = pool.id.clone();
+        self.commands.push(Command::SetPool(pool.id.clone()));
+
+        let _ = State {
+            pool_api_url: Some(self.config.pool_api_url.clone()),
+            pool_id: Some(pool.id.clone()),
+        }
+        .save();
     }
 
     fn exit(&mut self) {
         self.exit = true;
     }
 }
+
+/// helper function to create a centered rect of a given width percentage and height
+fn centered_rect(percent_x: u16, height: u16, area: Rect) -> Rect {
+    let vertical = Layout::new(
+        Direction::Vertical,
+        [
+            Constraint::Min(0),
+            Constraint::Length(height),
+            Constraint::Min(0),
+        ],
+    )
+    .split(area);
+
+    Layout::new(
+        Direction::Horizontal,
+        [
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ],
+    )
+    .split(vertical[1])[1]
+}
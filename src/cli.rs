@@ -0,0 +1,90 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use crate::config::{Config, Intervals, Watched};
+
+/// Terminal dashboard for the Sigmanauts Mining Pool
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// Wallet address to watch
+    #[arg(short, long)]
+    pub address: Option<String>,
+
+    /// Base URL of the Mining Core API, e.g. http://localhost:4000/api
+    #[arg(long)]
+    pub pool_url: Option<String>,
+
+    /// Id of the pool on the Mining Core instance
+    #[arg(long)]
+    pub pool_id: Option<String>,
+
+    /// Seconds between two refreshes of every source, replaces `[intervals]`
+    #[arg(short, long)]
+    pub interval: Option<u64>,
+
+    /// Path of the config file
+    #[arg(short, long)]
+    pub config: Option<PathBuf>,
+
+    /// Print the stats to stdout on every refresh instead of drawing the dashboard
+    #[arg(long)]
+    pub no_tui: bool,
+
+    /// Fetch the stats once, print them and exit
+    #[arg(long)]
+    pub once: bool,
+}
+
+impl Cli {
+    /// Override the values of the config with the ones given on the command line
+    pub fn apply(&self, config: &mut Config) {
+        //The address given on the command line is shown first, keeping its label if any
+        if let Some(address) = &self.address {
+            let watched = match config.addresses.iter().position(|w| &w.address == address) {
+                Some(i) => config.addresses.remove(i),
+                None => Watched::new(address),
+            };
+            config.addresses.insert(0, watched);
+        }
+
+        if let Some(pool_url) = &self.pool_url {
+            config.pool_api_url = pool_url.clone();
+        }
+
+        if let Some(pool_id) = &self.pool_id {
+            config.pool_id = pool_id.clone();
+        }
+
+        //A single interval for every source, the ones of the config file included
+        if let Some(interval) = self.interval {
+            config.refresh_interval = interval;
+            config.intervals = Intervals::default();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schedule::Source;
+
+    #[test]
+    fn interval_overrides_every_source() {
+        let mut config = Config {
+            intervals: Intervals {
+                pool: Some(30),
+                price: Some(300),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        Cli::parse_from(["smp-tui", "--interval", "15"]).apply(&mut config);
+
+        for source in Source::ALL {
+            assert_eq!(config.interval(source), 15);
+        }
+    }
+}
@@ -0,0 +1,266 @@
+use std::{fmt, fs, io, path::PathBuf};
+
+use serde::Deserialize;
+
+use crate::schedule::Source;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Base URL of the Mining Core API, without the `/pools` part
+    pub pool_api_url: String,
+    /// Id of the pool on the Mining Core instance
+    pub pool_id: String,
+    /// Ergo explorer API, used for the network hashrate and the oracle pool price
+    pub explorer_url: String,
+    /// Spectrum markets endpoint, used by the spectrum price source without a url
+    pub price_api_url: String,
+    /// Sources of the ERG price, tried in order until one answers
+    pub price_sources: Vec<PriceSource>,
+    /// Wallet addresses to watch, the first one is shown on start
+    pub addresses: Vec<Watched>,
+    /// Seconds between two refreshes of the data
+    pub refresh_interval: u64,
+    /// Seconds between two refreshes of a single source, `refresh_interval` when unset
+    pub intervals: Intervals,
+    /// Longest delay in seconds before a failing source is fetched again
+    pub max_backoff: u64,
+    /// Days of hashrate history kept on disk, 0 disables the history
+    pub history_retention_days: u64,
+    /// File the config was loaded from, `None` when the defaults are used
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            pool_api_url: "http://15.204.211.130:4000/api".to_string(),
+            pool_id: "ErgoSigmanauts".to_string(),
+            explorer_url: "https://api.ergoplatform.com".to_string(),
+            price_api_url: "https://api.spectrum.fi/v1/price-tracking/cmc/markets".to_string(),
+            price_sources: vec![PriceSource::Spectrum { url: None }],
+            addresses: Vec::new(),
+            refresh_interval: 60,
+            intervals: Intervals::default(),
+            max_backoff: 600,
+            history_retention_days: 30,
+            path: None,
+        }
+    }
+}
+
+/// Refresh interval of every source, in seconds
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Intervals {
+    pub pool: Option<u64>,
+    pub top_miners: Option<u64>,
+    pub network: Option<u64>,
+    pub price: Option<u64>,
+    pub blocks: Option<u64>,
+    pub miners: Option<u64>,
+}
+
+/// A source of the ERG price, see `price::PriceProvider`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum PriceSource {
+    /// ERG/SigUSD market of the Spectrum DEX
+    Spectrum {
+        /// `price_api_url` when unset
+        url: Option<String>,
+    },
+    /// CoinGecko or an API compatible with its `simple/price` endpoint
+    #[serde(rename = "coingecko")]
+    CoinGecko {
+        #[serde(default = "coingecko_label")]
+        label: String,
+        #[serde(default = "coingecko_url")]
+        url: String,
+        #[serde(default = "coingecko_id")]
+        id: String,
+        #[serde(default = "usd")]
+        currency: String,
+    },
+    /// Datapoint of an oracle pool, e.g. the ERG/USD one, read through the
+    /// explorer or a node
+    Oracle {
+        /// Token id of the pool NFT, held by the box of the last datapoint
+        nft: String,
+        /// Node API, e.g. `http://127.0.0.1:9053`, `explorer_url` is used when unset
+        node_url: Option<String>,
+    },
+    /// A fixed price
+    Static {
+        price: f64,
+        #[serde(default = "usd")]
+        currency: String,
+    },
+}
+
+fn coingecko_label() -> String {
+    "CoinGecko".to_string()
+}
+
+fn coingecko_url() -> String {
+    "https://api.coingecko.com/api/v3".to_string()
+}
+
+fn coingecko_id() -> String {
+    "ergo".to_string()
+}
+
+fn usd() -> String {
+    "USD".to_string()
+}
+
+/// A wallet address of the watchlist, with an optional label like "garage rigs"
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(from = "WatchedEntry")]
+pub struct Watched {
+    pub label: String,
+    pub address: String,
+}
+
+impl Watched {
+    pub fn new(address: &str) -> Watched {
+        Watched {
+            label: String::new(),
+            address: address.to_string(),
+        }
+    }
+}
+
+/// An entry of `addresses`, either a plain address or a labelled one
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum WatchedEntry {
+    Address(String),
+    Labelled {
+        #[serde(default)]
+        label: String,
+        address: String,
+    },
+}
+
+impl From<WatchedEntry> for Watched {
+    fn from(entry: WatchedEntry) -> Watched {
+        match entry {
+            WatchedEntry::Address(address) => Watched::new(&address),
+            WatchedEntry::Labelled { label, address } => Watched { label, address },
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(PathBuf, io::Error),
+    Parse(PathBuf, toml::de::Error),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Io(path, e) => write!(f, "Cannot read {}: {}", path.display(), e),
+            ConfigError::Parse(path, e) => write!(f, "Cannot parse {}: {}", path.display(), e),
+        }
+    }
+}
+
+impl Config {
+    /// Load the config from the given path, or from the XDG config dir.
+    /// A missing default config file is not an error, the defaults are used instead.
+    pub fn load(path: Option<PathBuf>) -> Result<Config, ConfigError> {
+        let (path, required) = match path {
+            Some(path) => (path, true),
+            None => match Config::default_path() {
+                Some(path) => (path, false),
+                None => return Ok(Config::default()),
+            },
+        };
+
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound && !required => {
+                return Ok(Config::default())
+            }
+            Err(e) => return Err(ConfigError::Io(path, e)),
+        };
+
+        let mut config: Config = match toml::from_str(&content) {
+            Ok(config) => config,
+            Err(e) => return Err(ConfigError::Parse(path, e)),
+        };
+        config.path = Some(path);
+
+        Ok(config)
+    }
+
+    /// `$XDG_CONFIG_HOME/smp-tui/config.toml`
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("smp-tui").join("config.toml"))
+    }
+
+    /// Seconds between two refreshes of `source`
+    pub fn interval(&self, source: Source) -> u64 {
+        let interval = match source {
+            Source::Pool => self.intervals.pool,
+            Source::TopMiners => self.intervals.top_miners,
+            Source::Network => self.intervals.network,
+            Source::Price => self.intervals.price,
+            Source::Blocks => self.intervals.blocks,
+            Source::Miners => self.intervals.miners,
+            // Only retries the charts that failed, nothing to tune
+            Source::Backfill => None,
+        };
+
+        interval.unwrap_or(self.refresh_interval)
+    }
+
+    /// Mining Core API URL of the configured pool
+    pub fn pool_url(&self) -> String {
+        format!(
+            "{}/pools/{}",
+            self.pool_api_url.trim_end_matches('/'),
+            self.pool_id
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_and_labelled_addresses() {
+        let config: Config = toml::from_str(
+            r#"
+            addresses = ["9f1", { label = "colo", address = "9f2" }]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.addresses[0], Watched::new("9f1"));
+        assert_eq!(
+            config.addresses[1],
+            Watched {
+                label: "colo".to_string(),
+                address: "9f2".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn load_keeps_the_path_of_the_file() {
+        let path = std::env::temp_dir().join(format!("smp-tui-{}.toml", std::process::id()));
+        fs::write(&path, "pool_id = \"other\"").unwrap();
+
+        let config = Config::load(Some(path.clone()));
+        fs::remove_file(&path).unwrap();
+
+        let config = config.unwrap();
+        assert_eq!(config.pool_id, "other");
+        assert_eq!(config.path, Some(path));
+    }
+}
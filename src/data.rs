@@ -1,196 +1,1541 @@
-use std::collections::VecDeque;
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap, VecDeque},
+    fmt,
+    sync::Arc,
+};
 
-use reqwest::{self, blocking::get};
+use chrono::{DateTime, TimeDelta, Utc};
+use futures::future::join_all;
+use reqwest::Client;
 
-#[derive(Debug, Default)]
+use crate::{
+    api::{
+        self, fetch, BlockStatus, Endpoint, ExplorerTransaction, MinerResponse, MinerSummary,
+        NetworkInfo, NetworkState, Payment, PerformanceSample, PoolBlock, PoolPerformanceResponse,
+        PoolResponse, PoolSummary, PoolsResponse,
+    },
+    config::{Config, Watched},
+    emission,
+    history::{History, Sample},
+    price::{self, PriceProvider},
+    schedule::Source,
+    units::{format_difficulty, format_hashrate},
+};
+
+/// Number of payments in a page of the Payments view
+pub const PAYMENTS_PAGE_SIZE: usize = 20;
+
+/// Number of payments in a page fetched for the totals
+const TOTALS_PAGE_SIZE: usize = 100;
+
+/// Seconds between two refreshes of the payment totals, they take a month of payments
+const TOTALS_INTERVAL: i64 = 600;
+
+/// Days of samples kept in the hashrate charts, the longest window of the dashboard
+const CHART_DAYS: i64 = 30;
+
+/// Points of a chart, a sample a minute over the longest window
+const CHART_CAPACITY: usize = CHART_DAYS as usize * 24 * 60;
+
+/// Points of a chart sampled on new blocks, a sample per block over the longest window
+const BLOCK_CHART_CAPACITY: usize = CHART_DAYS as usize * 86_400 / emission::BLOCK_TIME as usize;
+
+/// Deepest reorganisation of the chain accepted, a larger drop of the height
+/// is always an API node lagging behind
+const MAX_REORG_DEPTH: u64 = 10;
+
+/// Resolution of the points of a chart
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    /// Every sample
+    Raw,
+    /// The average of every 5 minutes
+    FiveMinutes,
+    /// The average of every hour
+    Hourly,
+}
+
+impl Resolution {
+    /// Seconds averaged in a point, `None` for the raw samples
+    fn seconds(self) -> Option<i64> {
+        match self {
+            Resolution::Raw => None,
+            Resolution::FiveMinutes => Some(300),
+            Resolution::Hourly => Some(3_600),
+        }
+    }
+}
+
+/// Hashrate samples of a chart, oldest first. It holds at most `capacity`
+/// samples, and none older than the longest chart window.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeSeries {
+    samples: VecDeque<Sample>,
+    capacity: usize,
+}
+
+#[derive(Debug, Default, Clone)]
 pub struct NetworkStats {
-    pub hashrate: VecDeque<(f64, f64)>,
+    /// One sample per new block
+    pub hashrate: TimeSeries,
+    /// H/s, refreshed more often than the chart
+    last_hashrate: f64,
     pub difficulty: f64,
     pub height: u64,
-    pub reward: u8,
-    pub reward_reduction: u8,
+    /// Lower height reported by the last refresh, a reorg if it is reported again
+    lower_height: Option<u64>,
+    pub reward: f64,
+    pub reward_reduction: u64,
     pub price: f64,
+    /// e.g. "SigUSD"
+    pub price_currency: String,
+    /// Label of the source of the price
+    pub price_source: String,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct PoolStats {
-    pub hashrate: VecDeque<(f64, f64)>,
+    /// One sample per new block
+    pub hashrate: TimeSeries,
+    /// H/s, refreshed more often than the chart
+    last_hashrate: f64,
     pub connected_miners: u64,
     pub effort: f64,
     pub total_blocks: u64,
     pub confirming_new_block: f64,
+    /// Expected seconds between two blocks found by the pool
+    pub block_time: f64,
+    /// Chance in % of the pool finding a block in the next hour
+    pub block_chance: f64,
+    /// Most recent blocks found by the pool
+    pub blocks: Vec<PoolBlock>,
+    /// Miners with the highest hashrate
+    pub top_miners: Vec<MinerSummary>,
+}
+
+/// What a block height reported by the pool means for the block-indexed data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HeightChange {
+    Unchanged,
+    /// A new block
+    Advanced,
+    /// Lower than the known height for the first time, ignored
+    Lagging,
+    /// The same lower height again, a reorg if the explorer confirms it,
+    /// ignored otherwise
+    PossibleReorg,
+    /// A lower height confirmed by the explorer, the chain was reorganised
+    Reorg,
+}
+
+/// Unexpected block height reported by the pool
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChainEvent {
+    /// The API node is behind the known height, its height is ignored
+    Lagging { known: u64, reported: u64 },
+    /// The chain went back to a lower height, the samples above it are replaced
+    Reorg { from: u64, to: u64 },
+}
+
+impl fmt::Display for ChainEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ChainEvent::Lagging { known, reported } => {
+                write!(f, "API lagging at block {} < {}", reported, known)
+            }
+            ChainEvent::Reorg { from, to } => write!(f, "Reorg from block {} to {}", from, to),
+        }
+    }
+}
+
+impl NetworkStats {
+    /// Last fetched hashrate, in H/s
+    pub fn current_hashrate(&self) -> f64 {
+        self.last_hashrate
+    }
+
+    /// Compare a height reported by the pool with the known one. A lower
+    /// height is a lagging or flapping API node, unless it is reported again
+    /// and the explorer confirms the chain went back, see `check_reorg`.
+    fn check_height(&mut self, reported: u64) -> HeightChange {
+        let change = match reported.cmp(&self.height) {
+            Ordering::Equal => HeightChange::Unchanged,
+            Ordering::Greater => HeightChange::Advanced,
+            Ordering::Less if self.lower_height != Some(reported) => HeightChange::Lagging,
+            Ordering::Less if self.height - reported <= MAX_REORG_DEPTH => {
+                HeightChange::PossibleReorg
+            }
+            // Still lagging, already reported
+            Ordering::Less => HeightChange::Unchanged,
+        };
+
+        self.lower_height = match change {
+            HeightChange::Lagging | HeightChange::PossibleReorg => Some(reported),
+            HeightChange::Unchanged if reported < self.height => self.lower_height,
+            _ => None,
+        };
+
+        change
+    }
+
+    /// A possible reorg is confirmed when the tip of the explorer is below
+    /// the known height too, the lower height is ignored until then
+    fn check_reorg(&mut self, explorer_height: Option<u64>) -> HeightChange {
+        match explorer_height {
+            Some(height) if height < self.height => {
+                self.lower_height = None;
+                HeightChange::Reorg
+            }
+            _ => HeightChange::Unchanged,
+        }
+    }
+}
+
+impl PoolStats {
+    /// Last fetched hashrate, in H/s
+    pub fn current_hashrate(&self) -> f64 {
+        self.last_hashrate
+    }
+
+    /// 1-based rank of `address` among the top miners
+    pub fn rank(&self, address: &str) -> Option<usize> {
+        self.top_miners
+            .iter()
+            .position(|miner| miner.miner == address)
+            .map(|i| i + 1)
+    }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct MinerStats {
-    pub hashrate: VecDeque<(f64, f64)>,
+    pub label: String,
+    pub address: String,
+    /// One sample per hour from Mining Core, then the current one of every refresh
+    pub hashrate: TimeSeries,
+    /// H/s
     pub average_hashrate: f64,
     pub pending_shares: f64,
     pub pending_balance: f64,
     pub round_contribution: f64,
     pub total_paid: f64,
+    pub workers: Vec<WorkerStats>,
+    pub payments: PaymentStats,
+    /// Confirmations of the payment transactions seen in a block, and the
+    /// height they were looked up at. They only grow with the height, so
+    /// they are not asked to the explorer again.
+    confirmed: HashMap<String, (u64, u64)>,
+    /// The chart was seeded, see `Stats::backfill`
+    backfilled: bool,
 }
 
-#[derive(Debug, Default)]
+/// Sum of the stats of every watched address
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MinerTotals {
+    /// H/s
+    pub hashrate: f64,
+    pub pending_balance: f64,
+    pub total_paid: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct WorkerStats {
+    pub name: String,
+    /// H/s
+    pub hashrate: f64,
+    /// H/s, over the last 24h
+    pub average_hashrate: f64,
+    pub shares_per_second: f64,
+    /// Time of the last sample with some hashrate
+    pub last_seen: Option<DateTime<Utc>>,
+    pub online: bool,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct PaymentStats {
+    /// Index of the page in `payments`, starting from the most recent one
+    pub page: usize,
+    pub payments: Vec<MinerPayment>,
+    pub total_day: f64,
+    pub total_week: f64,
+    pub total_month: f64,
+    /// When the totals were fetched
+    totals_updated: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MinerPayment {
+    pub payment: Payment,
+    pub confirmation: Confirmation,
+}
+
+/// State of the transaction of a payment, according to the explorer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Confirmation {
+    /// Unknown to the explorer, not in a block yet
+    Pending,
+    /// In a block, with that many confirmations
+    Confirmed(u64),
+    /// The explorer could not be asked
+    Unknown,
+}
+
+#[derive(Debug, Default, Clone)]
 pub struct Stats {
     pub network: NetworkStats,
     pub pool: PoolStats,
-    pub miner: MinerStats,
+    /// One entry per watched address, in the order of the config
+    pub miners: Vec<MinerStats>,
+    pools_api_url: String,
+    pool_api_url: String,
+    pool_id: String,
+    price_providers: Vec<Arc<dyn PriceProvider>>,
+    hashrate_api_url: String,
+    explorer_url: String,
+    client: Client,
+    history: Option<History>,
+    /// Chain events not taken yet, see `take_events`
+    events: Vec<ChainEvent>,
+    /// The network and pool charts were seeded, see `backfill`
+    backfilled: bool,
 }
 
 impl Stats {
-    pub fn default() -> Stats {
+    pub fn new(config: &Config) -> Stats {
         Stats {
-            network: NetworkStats::default(),
-            pool: PoolStats::default(),
-            miner: MinerStats::default(),
+            network: NetworkStats {
+                hashrate: TimeSeries::new(BLOCK_CHART_CAPACITY),
+                ..Default::default()
+            },
+            pool: PoolStats {
+                hashrate: TimeSeries::new(BLOCK_CHART_CAPACITY),
+                ..Default::default()
+            },
+            miners: config.addresses.iter().map(MinerStats::new).collect(),
+            pools_api_url: format!("{}/pools", config.pool_api_url.trim_end_matches('/')),
+            pool_api_url: config.pool_url(),
+            pool_id: config.pool_id.clone(),
+            price_providers: price::providers(config),
+            hashrate_api_url: format!("{}/info", config.explorer_url.trim_end_matches('/')),
+            explorer_url: config.explorer_url.trim_end_matches('/').to_string(),
+            client: Client::new(),
+            history: None,
+            events: Vec::new(),
+            backfilled: false,
+        }
+    }
+
+    /// Record the samples in `history` from now on, and fill the charts with
+    /// the last ones it holds
+    pub fn set_history(&mut self, history: History) {
+        let since = (Utc::now() - TimeDelta::days(CHART_DAYS)).timestamp();
+        let load = |series: &str| history.load(series, since).unwrap_or_default();
+
+        self.network.hashrate.extend(load("network"));
+        self.pool.hashrate.extend(load(&self.pool_series()));
+
+        self.history = Some(history);
+
+        for i in 0..self.miners.len() {
+            self.miners[i].hashrate = self.miner_history(&self.miners[i].address);
         }
     }
 
-    /// Get data from Mining Core API
-    pub fn get_data(&mut self) -> Result<(), reqwest::Error> {
-        let pool_api_url = "http://15.204.211.130:4000/api/pools/ErgoSigmanauts";
-        let price_api_url = "https://api.spectrum.fi/v1/price-tracking/cmc/markets";
-        let hashrate_api = "https://api.ergoplatform.com/info";
-        let data: serde_json::Value = get(pool_api_url)?.json()?;
+    /// Chart of a miner loaded from the history, empty without one
+    fn miner_history(&self, address: &str) -> TimeSeries {
+        let mut chart = TimeSeries::default();
 
-        //Format block height
-        let block_height = data["pool"]["networkStats"]["blockHeight"].clone().as_u64();
+        if let Some(history) = &self.history {
+            let since = (Utc::now() - TimeDelta::days(CHART_DAYS)).timestamp();
+            chart.extend(
+                history
+                    .load(&self.miner_series(address), since)
+                    .unwrap_or_default(),
+            );
+        }
 
-        //Only update the data if a new block is added to the chain
-        if block_height.unwrap() != self.network.height {
-            match block_height {
-                Some(block_height) => self.network.height = block_height,
-                None => println!("No data available for Block Height"),
+        chart
+    }
+
+    /// Store a sample in the history, a failed write only loses that sample
+    fn record(&self, series: &str, sample: Sample) {
+        if let Some(history) = &self.history {
+            let _ = history.record(series, &sample);
+        }
+    }
+
+    fn pool_series(&self) -> String {
+        format!("pool/{}", self.pool_id)
+    }
+
+    fn miner_series(&self, address: &str) -> String {
+        format!("miner/{}/{}", self.pool_id, address)
+    }
+
+    /// Stats of a watched address
+    pub fn miner(&self, address: &str) -> Option<&MinerStats> {
+        self.miners.iter().find(|miner| miner.address == address)
+    }
+
+    /// Add an address to the watchlist, returns false if it was already watched
+    pub fn watch(&mut self, watched: &Watched) -> bool {
+        if self.miner(&watched.address).is_some() {
+            return false;
+        }
+
+        let mut miner = MinerStats::new(watched);
+        miner.hashrate = self.miner_history(&watched.address);
+        self.miners.push(miner);
+        true
+    }
+
+    /// Current hashrate, pending balance and total paid of all the watched addresses
+    pub fn miner_totals(&self) -> MinerTotals {
+        let mut totals = self
+            .miners
+            .iter()
+            .fold(MinerTotals::default(), |totals, miner| MinerTotals {
+                hashrate: totals.hashrate + miner.current_hashrate(),
+                pending_balance: totals.pending_balance + miner.pending_balance,
+                total_paid: totals.total_paid + miner.total_paid,
+            });
+
+        totals.pending_balance = (totals.pending_balance * 100.0).round() / 100.0;
+        totals.total_paid = (totals.total_paid * 100.0).round() / 100.0;
+        totals
+    }
+
+    /// Get the pools hosted on the Mining Core instance
+    pub async fn get_pools(&self) -> Result<Vec<PoolSummary>, api::Error> {
+        let data: PoolsResponse = fetch(&self.client, Endpoint::Pools, &self.pools_api_url).await?;
+
+        Ok(data.pools)
+    }
+
+    /// Get the data of every source, one after the other. The charts are not
+    /// backfilled, the stats printed once have no use for them. Returns the
+    /// errors of the watched miners that failed, see `refresh`.
+    pub async fn get_data(&mut self) -> Result<Vec<api::Error>, api::Error> {
+        let mut errors = Vec::new();
+        for source in Source::ALL {
+            if source != Source::Backfill {
+                errors.extend(self.refresh(source).await?);
             }
+        }
 
-            let price_data: serde_json::Value = get(price_api_url)?.json()?;
-            let hashrate_data: serde_json::Value = get(hashrate_api)?.json()?;
+        Ok(errors)
+    }
 
-            // Network Hashrate
-            let network_hashrate = hashrate_data["hashRate"].clone().as_f64();
+    /// Get the data of a single source. A source made of several watched
+    /// miners only fails when all of them did, the errors of the others are
+    /// returned.
+    pub async fn refresh(&mut self, source: Source) -> Result<Vec<api::Error>, api::Error> {
+        let mut errors = Vec::new();
+        match source {
+            Source::Pool => self.get_pool_data().await?,
+            Source::TopMiners => self.get_top_miners().await?,
+            Source::Network => self.get_network_data().await?,
+            Source::Price => self.get_price().await?,
+            Source::Blocks => self.get_blocks().await?,
+            Source::Miners => errors = self.get_miners_data().await?,
+            Source::Backfill => self.backfill().await?,
+        }
 
-            match network_hashrate {
-                Some(network_hashrate) => {
-                    let network_hashrate =
-                        ((network_hashrate / 1_000_000_000_000.0) * 100.0).round() / 100.0;
-                    self.network
-                        .hashrate
-                        .push_back((block_height.unwrap() as f64, network_hashrate));
-                }
+        //Drop the samples past the retention of the history, once an hour
+        if let Some(history) = &self.history {
+            let _ = history.prune_hourly();
+        }
 
-                None => println!("No data available for Network Hashrate"),
+        //Store only the samples of the longest chart window
+        self.network.hashrate.trim();
+        self.pool.hashrate.trim();
+
+        Ok(errors)
+    }
+
+    /// Get the pool stats and the height of the chain from Mining Core API
+    async fn get_pool_data(&mut self) -> Result<(), api::Error> {
+        let data: PoolResponse = fetch(&self.client, Endpoint::Pool, &self.pool_api_url).await?;
+
+        //The charts get a sample per block, only when a new block is added to the chain
+        let reported = data.pool.network_stats.block_height;
+        let change = match self.network.check_height(reported) {
+            HeightChange::PossibleReorg => {
+                let explorer_height = self.get_explorer_height().await;
+                self.network.check_reorg(explorer_height)
             }
+            change => change,
+        };
+
+        match change {
+            HeightChange::Lagging => self.events.push(ChainEvent::Lagging {
+                known: self.network.height,
+                reported,
+            }),
+            HeightChange::Reorg => {
+                self.events.push(ChainEvent::Reorg {
+                    from: self.network.height,
+                    to: reported,
+                });
+                self.rewind(reported);
+            }
+            HeightChange::Unchanged | HeightChange::Advanced | HeightChange::PossibleReorg => {}
+        }
+
+        if matches!(change, HeightChange::Advanced | HeightChange::Reorg) {
+            self.network.height = reported;
+
+            // Block reward and blocks left before it is reduced
+            self.network.reward = emission::miner_reward_at_height(self.network.height) as f64
+                / emission::COINS_IN_ONE_ERG as f64;
+            self.network.reward_reduction = emission::next_reduction_height(self.network.height)
+                .map_or(0, |height| height - self.network.height);
+
+            //Pool hashrate
+            let sample = Sample {
+                timestamp: Utc::now().timestamp(),
+                height: self.network.height,
+                value: data.pool.pool_stats.pool_hashrate,
+            };
+            self.pool.hashrate.push(sample);
+            self.record(&self.pool_series(), sample);
+        }
+        self.pool.last_hashrate = data.pool.pool_stats.pool_hashrate;
+
+        // Network Difficulty
+        self.network.difficulty = data.pool.network_stats.network_difficulty;
+
+        //Pool expected time to find a block and chance to find one in the next hour,
+        //both 0 without hashrate
+        self.pool.block_time = emission::expected_block_time(
+            data.pool.network_stats.network_difficulty,
+            data.pool.pool_stats.pool_hashrate,
+        );
+        let block_chance = emission::block_chance(self.pool.block_time, 3_600.0);
+        self.pool.block_chance = (block_chance * 100.0).round() / 100.0;
+
+        //Pool connected miners
+        self.pool.connected_miners = data.pool.pool_stats.connected_miners;
 
-            // Network Difficulty
-            let network_difficulty = data["pool"]["networkStats"]["networkDifficulty"]
-                .clone()
-                .as_f64();
+        //Pool effort
+        self.pool.effort = (data.pool.pool_effort * 10000.0).round() / 100.0;
 
-            match network_difficulty {
-                Some(network_difficulty) => {
-                    //round to 2 decimals
-                    let network_difficulty =
-                        ((network_difficulty / 1_000_000_000_000_000.0) * 100.0).round() / 100.0;
-                    self.network.difficulty = network_difficulty;
+        //Pool total blocks
+        self.pool.total_blocks = data.pool.total_blocks;
+
+        Ok(())
+    }
+
+    /// Get the miners with the highest hashrate from Mining Core API
+    async fn get_top_miners(&mut self) -> Result<(), api::Error> {
+        self.pool.top_miners = fetch(
+            &self.client,
+            Endpoint::Miners,
+            &format!("{}/miners?page=0&pageSize=100", self.pool_api_url),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Height of the tip of the chain according to the explorer, `None` when
+    /// it cannot tell
+    async fn get_explorer_height(&self) -> Option<u64> {
+        let url = format!("{}/api/v1/networkState", self.explorer_url);
+        let state: Result<NetworkState, _> = fetch(&self.client, Endpoint::NetworkInfo, &url).await;
+
+        state.ok().map(|state| state.height)
+    }
+
+    /// Drop the samples of every chart taken at `height` or above, after a
+    /// reorg, so the new blocks replace them, from the history too
+    fn rewind(&mut self, height: u64) {
+        let mut rewound = vec![
+            ("network".to_string(), self.network.hashrate.rewind(height)),
+            (self.pool_series(), self.pool.hashrate.rewind(height)),
+        ];
+        for i in 0..self.miners.len() {
+            let series = self.miner_series(&self.miners[i].address);
+            rewound.push((series, self.miners[i].hashrate.rewind(height)));
+        }
+
+        if let Some(history) = &self.history {
+            for (series, since) in rewound {
+                if let Some(since) = since {
+                    let _ = history.forget(&series, since);
                 }
+            }
+        }
+    }
+
+    /// Unexpected heights reported by the pool since the last call
+    pub fn take_events(&mut self) -> Vec<ChainEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Get the network hashrate from the explorer
+    async fn get_network_data(&mut self) -> Result<(), api::Error> {
+        let data: NetworkInfo =
+            fetch(&self.client, Endpoint::NetworkInfo, &self.hashrate_api_url).await?;
+
+        self.network.last_hashrate = data.hash_rate;
 
-                None => println!("No data available for Network Difficulty"),
-            }
-
-            // ERG Price
-
-            if let serde_json::Value::Array(arr) = price_data {
-                for obj in arr {
-                    if let serde_json::Value::Object(o) = obj {
-                        if let Some(base_name) = o.get("base_name") {
-                            if base_name == "ERG" {
-                                if let Some(quote_name) = o.get("quote_name") {
-                                    if quote_name == "SigUSD" {
-                                        if let Some(last_price) = o.get("last_price") {
-                                            if let Some(price) = last_price.as_f64() {
-                                                self.network.price =
-                                                    ((1.0 / price) * 100.0).round() / 100.0;
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
+        //One sample per block, once the pool told the height
+        let last_height = self
+            .network
+            .hashrate
+            .last()
+            .map_or(0, |sample| sample.height);
+        if self.network.height > last_height {
+            let sample = Sample {
+                timestamp: Utc::now().timestamp(),
+                height: self.network.height,
+                value: data.hash_rate,
+            };
+            self.network.hashrate.push(sample);
+            self.record("network", sample);
+        }
+
+        Ok(())
+    }
+
+    /// Get the ERG price from the first price source answering
+    async fn get_price(&mut self) -> Result<(), api::Error> {
+        let (price, provider) = price::first_price(&self.price_providers, &self.client).await?;
+
+        self.network.price = (price * 100.0).round() / 100.0;
+        self.network.price_currency = provider.currency().to_string();
+        self.network.price_source = provider.label().to_string();
+
+        Ok(())
+    }
+
+    /// Get the most recent blocks found by the pool from Mining Core API
+    async fn get_blocks(&mut self) -> Result<(), api::Error> {
+        self.pool.blocks = fetch(
+            &self.client,
+            Endpoint::Blocks,
+            &format!("{}/blocks?page=0&pageSize=50", self.pool_api_url),
+        )
+        .await?;
+
+        //Pool confirming new block
+        self.pool.confirming_new_block = match self.pool.blocks.first() {
+            Some(block) if block.status == BlockStatus::Pending => {
+                block.confirmation_progress * 100.0
+            }
+            _ => 100.0,
+        };
+
+        Ok(())
+    }
+
+    /// Get the stats of all the watched miners, in parallel. Every miner is
+    /// refreshed on its own, returns the errors of the ones that failed, or
+    /// an error if all of them did.
+    async fn get_miners_data(&mut self) -> Result<Vec<api::Error>, api::Error> {
+        let pool_hashrate = self.pool.current_hashrate();
+        let height = self.network.height;
+        let results =
+            join_all(self.miners.iter_mut().map(|miner| {
+                miner.get_data(&self.client, &self.pool_api_url, pool_hashrate, height)
+            }))
+            .await;
+
+        let mut errors = Vec::new();
+        let mut refreshed = false;
+        for (miner, result) in self.miners.iter().zip(results) {
+            match result {
+                Ok(samples) => {
+                    refreshed = true;
+                    let series = self.miner_series(&miner.address);
+                    for sample in samples {
+                        self.record(&series, sample);
                     }
                 }
+                Err(e) => errors.push(e),
             }
+        }
 
-            //Pool hashrate
-            let pool_hashrate = data["pool"]["poolStats"]["poolHashrate"].clone().as_f64();
-
-            match pool_hashrate {
-                Some(pool_hashrate) => {
-                    let pool_hashrate = ((pool_hashrate / 1_000_000_000.0) * 100.0).round() / 100.0;
-                    self.pool
-                        .hashrate
-                        .push_back((self.network.height as f64, pool_hashrate))
-                }
+        if !refreshed {
+            if let Some(e) = errors.pop() {
+                return Err(e);
+            }
+        }
+
+        Ok(errors)
+    }
 
-                None => println!("No data available for Pool Hashrate"),
+    /// Get the stats of a single watched miner from Mining Core API
+    pub async fn get_miner_data(&mut self, address: &str) -> Result<(), api::Error> {
+        let pool_hashrate = self.pool.current_hashrate();
+        let height = self.network.height;
+
+        let samples = match self
+            .miners
+            .iter_mut()
+            .find(|miner| miner.address == address)
+        {
+            Some(miner) => {
+                miner
+                    .get_data(&self.client, &self.pool_api_url, pool_hashrate, height)
+                    .await?
             }
+            None => return Ok(()),
+        };
+
+        let series = self.miner_series(address);
+        for sample in samples {
+            self.record(&series, sample);
+        }
+
+        Ok(())
+    }
+
+    /// Seed the charts with the hourly samples of the last day and the daily
+    /// samples of the last month kept by Mining Core. Only the samples older
+    /// than the first point of a chart are added, so the live ones are kept.
+    /// Every chart is seeded once, on its own: the ones that failed are tried
+    /// again on the next call, after the error of the last one is returned.
+    pub async fn backfill(&mut self) -> Result<(), api::Error> {
+        //The heights of the samples are estimated from the current one
+        if self.network.height == 0 {
+            return Ok(());
+        }
 
-            //Pool connected miners
-            let connected_miners = data["pool"]["poolStats"]["connectedMiners"]
-                .clone()
-                .as_u64();
+        let mut result = Ok(());
 
-            match connected_miners {
-                Some(connected_miners) => self.pool.connected_miners = connected_miners,
+        if !self.backfilled {
+            match self.backfill_pool().await {
+                Ok(_) => self.backfilled = true,
+                Err(e) => result = Err(e),
+            }
+        }
 
-                None => println!("No data available for Connected Miners"),
+        let addresses: Vec<String> = self
+            .miners
+            .iter()
+            .filter(|miner| !miner.backfilled)
+            .map(|miner| miner.address.clone())
+            .collect();
+        let performances = join_all(
+            addresses
+                .iter()
+                .map(|address| self.get_miner_performance(address)),
+        )
+        .await;
+
+        for (address, performance) in addresses.iter().zip(performances) {
+            match performance {
+                Ok(performance) => self.backfill_miner(address, performance),
+                Err(e) => result = Err(e),
             }
+        }
 
-            //Pool effort
-            let pool_effort = data["pool"]["poolEffort"].clone().as_f64();
+        result
+    }
 
-            match pool_effort {
-                Some(pool_effort) => {
-                    let pool_effort = (pool_effort * 10000.0).round() / 100.0;
-                    self.pool.effort = pool_effort;
-                }
+    /// Seed the network and pool charts, see `backfill`
+    async fn backfill_pool(&mut self) -> Result<(), api::Error> {
+        let hourly_url = format!("{}/performance?r=Day&i=Hour", self.pool_api_url);
+        let daily_url = format!("{}/performance?r=Month&i=Day", self.pool_api_url);
+        let (hourly, daily) = tokio::join!(
+            fetch::<PoolPerformanceResponse>(&self.client, Endpoint::PoolPerformance, &hourly_url),
+            fetch::<PoolPerformanceResponse>(&self.client, Endpoint::PoolPerformance, &daily_url)
+        );
+        let (hourly, daily) = (hourly?.stats, daily?.stats);
+
+        //The hourly samples first, the daily ones only go further back
+        for samples in [hourly, daily] {
+            let network = samples
+                .iter()
+                .map(|sample| {
+                    estimated_sample(self.network.height, sample.created, sample.network_hashrate)
+                })
+                .collect();
+            let pool = samples
+                .iter()
+                .map(|sample| {
+                    estimated_sample(self.network.height, sample.created, sample.pool_hashrate)
+                })
+                .collect();
+
+            let network = self.network.hashrate.prepend(network);
+            let pool = self.pool.hashrate.prepend(pool);
+
+            for sample in network {
+                self.record("network", sample);
+            }
+            let series = self.pool_series();
+            for sample in pool {
+                self.record(&series, sample);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Hourly samples of the last day and daily samples of the last month of a miner
+    async fn get_miner_performance(
+        &self,
+        address: &str,
+    ) -> Result<[Vec<PerformanceSample>; 2], api::Error> {
+        let performance_url = format!("{}/miners/{}/performance", self.pool_api_url, address);
+        let hourly_url = format!("{}?mode=Day", performance_url);
+        let daily_url = format!("{}?mode=Month", performance_url);
+        let (hourly, daily) = tokio::join!(
+            fetch::<Vec<PerformanceSample>>(&self.client, Endpoint::MinerPerformance, &hourly_url),
+            fetch::<Vec<PerformanceSample>>(&self.client, Endpoint::MinerPerformance, &daily_url)
+        );
+
+        Ok([hourly?, daily?])
+    }
+
+    fn backfill_miner(&mut self, address: &str, performance: [Vec<PerformanceSample>; 2]) {
+        let Some(i) = self.miners.iter().position(|m| m.address == address) else {
+            return;
+        };
+        let series = self.miner_series(address);
+
+        for samples in performance {
+            let samples = samples
+                .iter()
+                .map(|sample| {
+                    estimated_sample(self.network.height, sample.created, sample.hashrate())
+                })
+                .collect();
 
-                None => println!("No data available for Pool Effort"),
+            let added = self.miners[i].hashrate.prepend(samples);
+            for sample in added {
+                self.record(&series, sample);
             }
+        }
+        self.miners[i].backfilled = true;
+    }
+
+    /// Get a page of the payments of a miner from Mining Core API, and the
+    /// confirmations of their transactions from the explorer. The totals are
+    /// fetched again once they are `TOTALS_INTERVAL` old.
+    pub async fn get_payments(&mut self, address: &str, page: usize) -> Result<(), api::Error> {
+        let Some(miner) = self
+            .miners
+            .iter_mut()
+            .find(|miner| miner.address == address)
+        else {
+            return Ok(());
+        };
+        let payments_url = format!("{}/miners/{}/payments", self.pool_api_url, address);
+
+        //Requested page
+        let payments: Vec<Payment> = fetch(
+            &self.client,
+            Endpoint::Payments,
+            &format!(
+                "{}?page={}&pageSize={}",
+                payments_url, page, PAYMENTS_PAGE_SIZE
+            ),
+        )
+        .await?;
+
+        //Transactions confirmations, only the ones not seen in a block yet are
+        //asked to the explorer
+        let height = self.network.height;
+        let mut ids: Vec<&String> = payments
+            .iter()
+            .map(|payment| &payment.transaction_confirmation_data)
+            .filter(|id| !miner.confirmed.contains_key(*id))
+            .collect();
+        ids.sort();
+        ids.dedup();
 
-            //Pool total blocks
-            let pool_total_blocks = data["pool"]["totalBlocks"].clone().as_u64();
+        let transaction_urls: Vec<String> = ids
+            .iter()
+            .map(|id| format!("{}/api/v1/transactions/{}", self.explorer_url, id))
+            .collect();
+        let transactions =
+            join_all(transaction_urls.iter().map(|url| {
+                fetch::<ExplorerTransaction>(&self.client, Endpoint::Transactions, url)
+            }))
+            .await;
+
+        //A transaction unknown to the explorer is still pending, any other
+        //error tells nothing
+        let mut lookups: HashMap<String, Confirmation> = HashMap::new();
+        for (id, transaction) in ids.into_iter().zip(transactions) {
+            let confirmation = match transaction {
+                Ok(transaction) => {
+                    miner
+                        .confirmed
+                        .insert(id.clone(), (transaction.num_confirmations, height));
+                    Confirmation::Confirmed(transaction.num_confirmations)
+                }
+                Err(e) if e.is_not_found() => Confirmation::Pending,
+                Err(_) => Confirmation::Unknown,
+            };
+            lookups.insert(id.clone(), confirmation);
+        }
+
+        miner.payments.page = page;
+        miner.payments.payments = payments
+            .into_iter()
+            .map(|payment| {
+                let id = &payment.transaction_confirmation_data;
+                let confirmation = match miner.confirmed.get(id) {
+                    Some(&(confirmations, seen_at)) => {
+                        Confirmation::Confirmed(confirmations + height.saturating_sub(seen_at))
+                    }
+                    None => lookups.get(id).copied().unwrap_or(Confirmation::Unknown),
+                };
 
-            match pool_total_blocks {
-                Some(pool_total_blocks) => {
-                    self.pool.total_blocks = pool_total_blocks;
+                MinerPayment {
+                    payment,
+                    confirmation,
                 }
+            })
+            .collect();
 
-                None => println!("No data available for Pool Effort"),
+        let outdated = miner
+            .payments
+            .totals_updated
+            .is_none_or(|updated| Utc::now() - updated >= TimeDelta::seconds(TOTALS_INTERVAL));
+        if outdated {
+            miner
+                .payments
+                .get_totals(&self.client, &payments_url)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl PaymentStats {
+    /// Get the totals over the last day, week and month from Mining Core API,
+    /// the pages of payments are fetched until one is older than a month
+    async fn get_totals(&mut self, client: &Client, payments_url: &str) -> Result<(), api::Error> {
+        let now = Utc::now();
+        let mut recent: Vec<Payment> = Vec::new();
+
+        for page in 0.. {
+            let payments: Vec<Payment> = fetch(
+                client,
+                Endpoint::Payments,
+                &format!(
+                    "{}?page={}&pageSize={}",
+                    payments_url, page, TOTALS_PAGE_SIZE
+                ),
+            )
+            .await?;
+
+            let last_page = payments.len() < TOTALS_PAGE_SIZE
+                || payments
+                    .last()
+                    .is_none_or(|payment| now - payment.created > TimeDelta::days(30));
+            recent.extend(payments);
+
+            if last_page {
+                break;
             }
+        }
 
-            //Pool confirming new block
+        let total_since = |days: i64| -> f64 {
+            let total: f64 = recent
+                .iter()
+                .filter(|payment| now - payment.created <= TimeDelta::days(days))
+                .map(|payment| payment.amount)
+                .sum();
+            (total * 100.0).round() / 100.0
+        };
 
-            let block_data: serde_json::Value = get(format!("{}/blocks", pool_api_url))?.json()?;
+        self.total_day = total_since(1);
+        self.total_week = total_since(7);
+        self.total_month = total_since(30);
+        self.totals_updated = Some(now);
 
-            let pool_block_confirmation: (&str, f64) = (
-                block_data[0]["status"].as_str().unwrap(),
-                (block_data[0]["confirmationProgress"].as_f64().unwrap()) * 100.0,
-            );
+        Ok(())
+    }
+}
+
+impl MinerStats {
+    fn new(watched: &Watched) -> MinerStats {
+        MinerStats {
+            label: watched.label.clone(),
+            address: watched.address.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Last hashrate sample, in H/s
+    pub fn current_hashrate(&self) -> f64 {
+        self.hashrate.last().map_or(0.0, |sample| sample.value)
+    }
+
+    /// Get the stats of the miner from Mining Core API, `pool_hashrate` in H/s.
+    /// Returns the fetched hashrate samples, to be stored in the history.
+    async fn get_data(
+        &mut self,
+        client: &Client,
+        pool_api_url: &str,
+        pool_hashrate: f64,
+        height: u64,
+    ) -> Result<Vec<Sample>, api::Error> {
+        let data: MinerResponse = fetch(
+            client,
+            Endpoint::Miner,
+            &format!("{}/miners/{}", pool_api_url, self.address),
+        )
+        .await?;
+
+        //Miner pending shares
+        self.pending_shares = (data.pending_shares * 100.0).round() / 100.0;
 
-            if pool_block_confirmation.0 == "pending" {
-                self.pool.confirming_new_block = pool_block_confirmation.1;
+        //Miner pending balance
+        self.pending_balance = (data.pending_balance * 100.0).round() / 100.0;
+
+        //Miner total paid
+        self.total_paid = (data.total_paid * 100.0).round() / 100.0;
+
+        //Miner hashrate history, one sample per hour for the last 24h, replacing
+        //the samples of the same period, the live ones after it are kept
+        let samples: Vec<Sample> = data
+            .performance_samples
+            .iter()
+            .map(|sample| estimated_sample(height, sample.created, sample.hashrate()))
+            .collect();
+
+        self.hashrate.replace(&samples);
+
+        //Miner current hashrate, no performance means all the workers are offline
+        let current = match &data.performance {
+            Some(performance) => {
+                estimated_sample(height, performance.created, performance.hashrate())
+            }
+            None => estimated_sample(height, Utc::now(), 0.0),
+        };
+        self.hashrate.push(current);
+
+        //Miner average hashrate over the last 24h
+        let day_ago = (Utc::now() - TimeDelta::days(1)).timestamp();
+        self.average_hashrate = self.hashrate.average(day_ago).unwrap_or(0.0);
+
+        //Miner workers
+        self.workers = worker_stats(&data);
+
+        //Miner round contribution, as share of the pool hashrate
+        let miner_hashrate = self.current_hashrate();
+
+        if pool_hashrate > 0.0 {
+            let round_contribution = miner_hashrate / pool_hashrate * 100.0;
+            self.round_contribution = (round_contribution * 100.0).round() / 100.0;
+        }
+
+        //Samples for the history, the current one included
+        Ok(samples
+            .into_iter()
+            .chain(data.performance.is_some().then_some(current))
+            .collect())
+    }
+}
+
+/// Current and 24h stats of every worker seen in the samples of a miner
+fn worker_stats(data: &MinerResponse) -> Vec<WorkerStats> {
+    let mut workers: BTreeMap<&str, WorkerStats> = BTreeMap::new();
+    let samples = data
+        .performance_samples
+        .iter()
+        .chain(data.performance.iter());
+
+    for sample in samples {
+        for (name, performance) in &sample.workers {
+            let worker = workers.entry(name).or_insert_with(|| WorkerStats {
+                name: if name.is_empty() {
+                    "default".to_string()
+                } else {
+                    name.clone()
+                },
+                hashrate: 0.0,
+                average_hashrate: 0.0,
+                shares_per_second: 0.0,
+                last_seen: None,
+                online: false,
+            });
+
+            if performance.hashrate > 0.0 {
+                worker.last_seen = worker.last_seen.max(Some(sample.created));
+            }
+        }
+    }
+
+    let sample_count = data.performance_samples.len().max(1) as f64;
+
+    for (name, worker) in workers.iter_mut() {
+        let total: f64 = data
+            .performance_samples
+            .iter()
+            .filter_map(|sample| sample.workers.get(*name))
+            .map(|performance| performance.hashrate)
+            .sum();
+        worker.average_hashrate = total / sample_count;
+
+        if let Some(performance) = data
+            .performance
+            .as_ref()
+            .and_then(|sample| sample.workers.get(*name))
+        {
+            worker.hashrate = performance.hashrate;
+            worker.shares_per_second = performance.shares_per_second;
+            worker.online = performance.hashrate > 0.0;
+        }
+    }
+
+    workers.into_values().collect()
+}
+
+/// Sample of a past time, at the height the chain was expected to have then
+fn estimated_sample(height: u64, created: DateTime<Utc>, hashrate: f64) -> Sample {
+    let age = (Utc::now() - created).num_seconds().max(0) as u64;
+
+    Sample {
+        timestamp: created.timestamp(),
+        height: height.saturating_sub(age / emission::BLOCK_TIME),
+        value: hashrate,
+    }
+}
+
+impl Default for TimeSeries {
+    fn default() -> TimeSeries {
+        TimeSeries::new(CHART_CAPACITY)
+    }
+}
+
+impl TimeSeries {
+    pub fn new(capacity: usize) -> TimeSeries {
+        TimeSeries {
+            samples: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    /// Most recent sample
+    pub fn last(&self) -> Option<&Sample> {
+        self.samples.back()
+    }
+
+    /// Samples taken since the `since` unix timestamp
+    pub fn since(&self, since: i64) -> impl DoubleEndedIterator<Item = &Sample> {
+        let start = self
+            .samples
+            .partition_point(|sample| sample.timestamp < since);
+
+        self.samples.range(start..)
+    }
+
+    /// Lowest value since the `since` unix timestamp
+    pub fn min(&self, since: i64) -> Option<f64> {
+        self.since(since)
+            .map(|sample| sample.value)
+            .reduce(f64::min)
+    }
+
+    /// Highest value since the `since` unix timestamp
+    pub fn max(&self, since: i64) -> Option<f64> {
+        self.since(since)
+            .map(|sample| sample.value)
+            .reduce(f64::max)
+    }
+
+    /// Average value since the `since` unix timestamp
+    pub fn average(&self, since: i64) -> Option<f64> {
+        let (count, total) = self.since(since).fold((0, 0.0), |(count, total), sample| {
+            (count + 1, total + sample.value)
+        });
+
+        (count > 0).then(|| total / count as f64)
+    }
+
+    /// Samples since the `since` unix timestamp at `resolution`. A rolled up
+    /// point averages the values of its period, at the time and height of
+    /// its last sample.
+    pub fn rollup(&self, resolution: Resolution, since: i64) -> Vec<Sample> {
+        let Some(period) = resolution.seconds() else {
+            return self.since(since).copied().collect();
+        };
+
+        let mut points: Vec<Sample> = Vec::new();
+        let mut count = 0;
+
+        for sample in self.since(since) {
+            match points.last_mut() {
+                Some(point)
+                    if point.timestamp.div_euclid(period)
+                        == sample.timestamp.div_euclid(period) =>
+                {
+                    count += 1;
+                    point.value += (sample.value - point.value) / count as f64;
+                    point.timestamp = sample.timestamp;
+                    point.height = sample.height;
+                }
+                _ => {
+                    points.push(*sample);
+                    count = 1;
+                }
+            }
+        }
+
+        points
+    }
+
+    /// Add a sample after the others, it replaces the last one if it was
+    /// taken at the same time
+    fn push(&mut self, sample: Sample) {
+        if self
+            .samples
+            .back()
+            .is_some_and(|last| last.timestamp == sample.timestamp)
+        {
+            self.samples.pop_back();
+        }
+        self.samples.push_back(sample);
+        self.trim();
+    }
+
+    /// Replace the samples taken from the first to the last of `samples`
+    /// with them, the ones taken after are kept
+    fn replace(&mut self, samples: &[Sample]) {
+        let (Some(first), Some(last)) = (samples.first(), samples.last()) else {
+            return;
+        };
+
+        let start = self
+            .samples
+            .partition_point(|sample| sample.timestamp < first.timestamp);
+        let end = self
+            .samples
+            .partition_point(|sample| sample.timestamp <= last.timestamp);
+        let later = self.samples.split_off(end.max(start));
+        self.samples.truncate(start);
+
+        self.extend(samples.iter().copied().chain(later));
+    }
+
+    /// Drop the last samples taken at `height` or above, returns the time of
+    /// the oldest one dropped
+    fn rewind(&mut self, height: u64) -> Option<i64> {
+        let mut since = None;
+        while self
+            .samples
+            .back()
+            .is_some_and(|sample| sample.height >= height)
+        {
+            since = self.samples.pop_back().map(|sample| sample.timestamp);
+        }
+
+        since
+    }
+
+    /// Put the samples older than the first one in front of it, returns the
+    /// ones added
+    fn prepend(&mut self, mut samples: Vec<Sample>) -> Vec<Sample> {
+        let first = self
+            .samples
+            .front()
+            .map_or(i64::MAX, |sample| sample.timestamp);
+
+        samples.sort_by_key(|sample| sample.timestamp);
+        samples.retain(|sample| sample.timestamp < first);
+
+        for sample in samples.iter().rev() {
+            self.samples.push_front(*sample);
+        }
+        self.trim();
+
+        samples
+    }
+
+    /// Drop the samples older than the longest chart window, then the oldest
+    /// ones past the capacity
+    fn trim(&mut self) {
+        let oldest = (Utc::now() - TimeDelta::days(CHART_DAYS)).timestamp();
+        let start = self
+            .samples
+            .partition_point(|sample| sample.timestamp < oldest);
+        self.samples.drain(..start);
+
+        let excess = self.samples.len().saturating_sub(self.capacity);
+        self.samples.drain(..excess);
+    }
+}
+
+impl Extend<Sample> for TimeSeries {
+    /// Add samples after the others, oldest first
+    fn extend<T: IntoIterator<Item = Sample>>(&mut self, samples: T) {
+        self.samples.extend(samples);
+        self.trim();
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Network")?;
+        writeln!(
+            f,
+            "  Hashrate:           {}",
+            format_hashrate(self.network.current_hashrate())
+        )?;
+        writeln!(
+            f,
+            "  Difficulty:         {}",
+            format_difficulty(self.network.difficulty)
+        )?;
+        writeln!(f, "  Block Height:       {}", self.network.height)?;
+        writeln!(f, "  Block Reward:       {} Σ", self.network.reward)?;
+        writeln!(
+            f,
+            "  Reward Reduction:   {} blocks",
+            self.network.reward_reduction
+        )?;
+        writeln!(
+            f,
+            "  ERG Price:          {} {} ({})",
+            self.network.price, self.network.price_currency, self.network.price_source
+        )?;
+
+        writeln!(f, "Pool")?;
+        writeln!(
+            f,
+            "  Hashrate:           {}",
+            format_hashrate(self.pool.current_hashrate())
+        )?;
+        writeln!(f, "  Connected Miners:   {}", self.pool.connected_miners)?;
+        writeln!(f, "  Current Effort:     {} %", self.pool.effort)?;
+        writeln!(f, "  Block Found Every:  {:.0} s", self.pool.block_time)?;
+        writeln!(f, "  Block Chance (1h):  {} %", self.pool.block_chance)?;
+        writeln!(f, "  Blocks Found:       {}", self.pool.total_blocks)?;
+        writeln!(
+            f,
+            "  Confirming Block:   {} %",
+            self.pool.confirming_new_block
+        )?;
+
+        for miner in &self.miners {
+            if miner.label.is_empty() {
+                writeln!(f, "Miner {}", miner.address)?;
             } else {
-                self.pool.confirming_new_block = 100.0;
+                writeln!(f, "Miner {} ({})", miner.label, miner.address)?;
             }
+            writeln!(
+                f,
+                "  Hashrate:           {}",
+                format_hashrate(miner.current_hashrate())
+            )?;
+            writeln!(
+                f,
+                "  Average Hashrate:   {}",
+                format_hashrate(miner.average_hashrate)
+            )?;
+            writeln!(f, "  Round Contribution: {} %", miner.round_contribution)?;
+            writeln!(f, "  Pending Shares:     {}", miner.pending_shares)?;
+            writeln!(f, "  Pending Balance:    {} Σ", miner.pending_balance)?;
+            writeln!(f, "  Total Paid:         {} Σ", miner.total_paid)?;
         }
 
-        //Store only the last 720 blocks (720 * 2min = 24h)
-        if self.network.hashrate.len() > 720 {
-            self.network.hashrate.pop_front();
-            self.pool.hashrate.pop_front();
+        if self.miners.len() > 1 {
+            let totals = self.miner_totals();
+            writeln!(f, "Watchlist Total")?;
+            writeln!(
+                f,
+                "  Hashrate:           {}",
+                format_hashrate(totals.hashrate)
+            )?;
+            writeln!(f, "  Pending Balance:    {} Σ", totals.pending_balance)?;
+            writeln!(f, "  Total Paid:         {} Σ", totals.total_paid)?;
         }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(timestamp: i64, value: f64) -> Sample {
+        Sample {
+            timestamp,
+            height: 0,
+            value,
+        }
+    }
+
+    fn series(samples: &[Sample]) -> TimeSeries {
+        let mut series = TimeSeries::default();
+        series.extend(samples.iter().copied());
+        series
+    }
+
+    #[test]
+    fn prepend_keeps_the_live_samples() {
+        let now = Utc::now().timestamp();
+        let mut chart = series(&[sample(now - 20, 3.0), sample(now - 10, 4.0)]);
+
+        let added = chart.prepend(vec![
+            sample(now - 15, 9.0),
+            sample(now - 30, 2.0),
+            sample(now - 40, 1.0),
+        ]);
+
+        assert_eq!(added, vec![sample(now - 40, 1.0), sample(now - 30, 2.0)]);
+        assert_eq!(
+            chart,
+            series(&[
+                sample(now - 40, 1.0),
+                sample(now - 30, 2.0),
+                sample(now - 20, 3.0),
+                sample(now - 10, 4.0),
+            ])
+        );
+    }
+
+    #[test]
+    fn replace_keeps_the_live_samples_after_the_hourly_ones() {
+        let now = Utc::now().timestamp();
+        let mut chart = series(&[
+            sample(now - 7_300, 1.0),
+            sample(now - 7_200, 2.0),
+            sample(now - 3_660, 3.0),
+            sample(now - 3_000, 4.0),
+            sample(now - 60, 5.0),
+        ]);
+
+        chart.replace(&[sample(now - 7_200, 20.0), sample(now - 3_600, 30.0)]);
+        chart.push(sample(now, 6.0));
+        chart.push(sample(now, 7.0));
+
+        assert_eq!(
+            chart,
+            series(&[
+                sample(now - 7_300, 1.0),
+                sample(now - 7_200, 20.0),
+                sample(now - 3_600, 30.0),
+                sample(now - 3_000, 4.0),
+                sample(now - 60, 5.0),
+                sample(now, 7.0),
+            ])
+        );
+    }
+
+    #[test]
+    fn trim_drops_samples_older_than_the_longest_window() {
+        let now = Utc::now().timestamp();
+        let chart = series(&[
+            sample(now - (CHART_DAYS + 1) * 86_400, 1.0),
+            sample(now, 2.0),
+        ]);
+
+        assert_eq!(chart.since(0).collect::<Vec<_>>(), vec![&sample(now, 2.0)]);
+    }
+
+    #[test]
+    fn capacity_drops_the_oldest_samples() {
+        let now = Utc::now().timestamp();
+        let mut chart = TimeSeries::new(2);
+
+        for i in 0..4 {
+            chart.push(sample(now + i, i as f64));
+        }
+
+        assert_eq!(chart.since(0).count(), 2);
+        assert_eq!(chart.since(0).next(), Some(&sample(now + 2, 2.0)));
+    }
+
+    #[test]
+    fn queries_since_a_timestamp() {
+        let now = Utc::now().timestamp();
+        let chart = series(&[
+            sample(now - 30, 10.0),
+            sample(now - 20, 1.0),
+            sample(now - 10, 5.0),
+        ]);
+
+        assert_eq!(chart.min(now - 20), Some(1.0));
+        assert_eq!(chart.max(now - 20), Some(5.0));
+        assert_eq!(chart.average(now - 20), Some(3.0));
+        assert_eq!(chart.max(now), None);
+        assert_eq!(chart.average(now), None);
+    }
+
+    #[test]
+    fn rewind_drops_the_samples_of_the_replaced_blocks() {
+        let now = Utc::now().timestamp();
+        let at = |timestamp, height| Sample {
+            timestamp,
+            height,
+            value: 1.0,
+        };
+        let mut chart = series(&[at(now - 30, 99), at(now - 20, 100), at(now - 10, 101)]);
+
+        assert_eq!(chart.rewind(100), Some(now - 20));
+        assert_eq!(chart.since(0).collect::<Vec<_>>(), vec![&at(now - 30, 99)]);
+        assert_eq!(chart.rewind(100), None);
+    }
+
+    #[test]
+    fn lower_heights_are_lag_until_the_explorer_confirms_a_reorg() {
+        let mut network = NetworkStats::default();
+
+        assert_eq!(network.check_height(100), HeightChange::Advanced);
+        network.height = 100;
+        assert_eq!(network.check_height(100), HeightChange::Unchanged);
+
+        // A flapping node
+        assert_eq!(network.check_height(99), HeightChange::Lagging);
+        assert_eq!(network.check_height(100), HeightChange::Unchanged);
+        assert_eq!(network.check_height(99), HeightChange::Lagging);
+
+        // The same lower height again, the explorer is still at 100
+        assert_eq!(network.check_height(99), HeightChange::PossibleReorg);
+        assert_eq!(network.check_reorg(Some(100)), HeightChange::Unchanged);
+        assert_eq!(network.check_height(99), HeightChange::PossibleReorg);
+        assert_eq!(network.check_reorg(None), HeightChange::Unchanged);
+
+        // The explorer went back too
+        assert_eq!(network.check_height(99), HeightChange::PossibleReorg);
+        assert_eq!(network.check_reorg(Some(99)), HeightChange::Reorg);
+        network.height = 99;
+        assert_eq!(network.check_height(100), HeightChange::Advanced);
+        network.height = 100;
+
+        // Too deep for a reorg, reported once
+        assert_eq!(network.check_height(50), HeightChange::Lagging);
+        assert_eq!(network.check_height(50), HeightChange::Unchanged);
+        assert_eq!(network.check_height(50), HeightChange::Unchanged);
+        assert_eq!(network.check_height(101), HeightChange::Advanced);
+    }
+
+    #[test]
+    fn rollups_average_every_period() {
+        // Start of an hour, a few hours ago
+        let hour = (Utc::now().timestamp() - 3 * 3_600).div_euclid(3_600) * 3_600;
+        let chart = series(&[
+            sample(hour, 1.0),
+            sample(hour + 60, 2.0),
+            sample(hour + 240, 3.0),
+            sample(hour + 300, 4.0),
+            sample(hour + 3_600, 8.0),
+        ]);
+
+        assert_eq!(chart.rollup(Resolution::Raw, 0).len(), 5);
+        assert_eq!(
+            chart.rollup(Resolution::FiveMinutes, 0),
+            vec![
+                sample(hour + 240, 2.0),
+                sample(hour + 300, 4.0),
+                sample(hour + 3_600, 8.0),
+            ]
+        );
+        assert_eq!(
+            chart.rollup(Resolution::Hourly, hour + 60),
+            vec![sample(hour + 300, 3.0), sample(hour + 3_600, 8.0)]
+        );
+    }
+}
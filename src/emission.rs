@@ -0,0 +1,202 @@
+//! Ergo emission schedule, including the EIP-27 re-emission rules, and the
+//! odds of finding a block. All the amounts are in nanoERG.
+
+pub const COINS_IN_ONE_ERG: u64 = 1_000_000_000;
+
+/// Target time between two blocks, in seconds
+pub const BLOCK_TIME: u64 = 120;
+
+/// Blocks emitting the fixed rate, about 2 years
+const FIXED_RATE_PERIOD: u64 = 525_600;
+const FIXED_RATE: u64 = 75 * COINS_IN_ONE_ERG;
+/// Share of the fixed rate going to the foundation
+const FOUNDERS_INITIAL_REWARD: u64 = 7_500_000_000;
+/// Blocks between two reductions, about 90 days
+const EPOCH_LENGTH: u64 = 64_800;
+const ONE_EPOCH_REDUCTION: u64 = 3 * COINS_IN_ONE_ERG;
+
+/// EIP-27: part of the emission is locked in the re-emission contract
+const REEMISSION_ACTIVATION_HEIGHT: u64 = 777_217;
+/// EIP-27: the re-emission contract starts paying the miners once the emission ends
+const REEMISSION_START_HEIGHT: u64 = 2_080_800;
+const BASIC_CHARGE_AMOUNT: u64 = 12 * COINS_IN_ONE_ERG;
+const REEMISSION_REWARD: u64 = 3 * COINS_IN_ONE_ERG;
+
+/// Coins emitted by the block at `height`, before the re-emission charge
+pub fn emission_at_height(height: u64) -> u64 {
+    if height < FIXED_RATE_PERIOD {
+        FIXED_RATE
+    } else {
+        let epoch = 1 + (height - FIXED_RATE_PERIOD) / EPOCH_LENGTH;
+        FIXED_RATE.saturating_sub(ONE_EPOCH_REDUCTION * epoch)
+    }
+}
+
+/// Coins of the emission sent to the re-emission contract (EIP-27)
+pub fn reemission_at_height(height: u64) -> u64 {
+    let emission = emission_at_height(height);
+
+    if height < REEMISSION_ACTIVATION_HEIGHT {
+        0
+    } else if emission >= BASIC_CHARGE_AMOUNT + REEMISSION_REWARD {
+        BASIC_CHARGE_AMOUNT
+    } else {
+        emission.saturating_sub(REEMISSION_REWARD)
+    }
+}
+
+/// Height of the first block not paid by the re-emission contract anymore
+fn reemission_end_height() -> u64 {
+    let mut total = 0;
+    let mut height = REEMISSION_ACTIVATION_HEIGHT;
+
+    // The charge is constant within an epoch
+    while height < REEMISSION_START_HEIGHT {
+        let epoch_end = next_epoch_height(height).min(REEMISSION_START_HEIGHT);
+        total += reemission_at_height(height) * (epoch_end - height);
+        height = epoch_end;
+    }
+
+    REEMISSION_START_HEIGHT + total / REEMISSION_REWARD
+}
+
+/// Height of the first block of the epoch following the one of `height`
+fn next_epoch_height(height: u64) -> u64 {
+    if height < FIXED_RATE_PERIOD {
+        FIXED_RATE_PERIOD
+    } else {
+        FIXED_RATE_PERIOD + ((height - FIXED_RATE_PERIOD) / EPOCH_LENGTH + 1) * EPOCH_LENGTH
+    }
+}
+
+/// Coins received by the miner of the block at `height`
+pub fn miner_reward_at_height(height: u64) -> u64 {
+    if height >= REEMISSION_START_HEIGHT {
+        return if height < reemission_end_height() {
+            REEMISSION_REWARD
+        } else {
+            0
+        };
+    }
+
+    let reward = if height < FIXED_RATE_PERIOD + 2 * EPOCH_LENGTH {
+        FIXED_RATE - FOUNDERS_INITIAL_REWARD
+    } else {
+        emission_at_height(height)
+    };
+
+    reward - reemission_at_height(height)
+}
+
+/// Height of the next block paying the miner less than the block at `height`,
+/// `None` once the rewards have ended
+pub fn next_reduction_height(height: u64) -> Option<u64> {
+    let reward = miner_reward_at_height(height);
+    let reemission_end = reemission_end_height();
+    let mut candidate = height;
+
+    while candidate < reemission_end {
+        candidate = if candidate < REEMISSION_START_HEIGHT {
+            let mut next = next_epoch_height(candidate);
+            if candidate < REEMISSION_ACTIVATION_HEIGHT {
+                next = next.min(REEMISSION_ACTIVATION_HEIGHT);
+            }
+            next
+        } else {
+            reemission_end
+        };
+
+        if miner_reward_at_height(candidate) != reward {
+            return Some(candidate);
+        }
+    }
+
+    None
+}
+
+/// Expected seconds between two blocks found with `hashrate` H/s at
+/// `difficulty`, 0 without hashrate
+pub fn expected_block_time(difficulty: f64, hashrate: f64) -> f64 {
+    if hashrate > 0.0 {
+        difficulty / hashrate
+    } else {
+        0.0
+    }
+}
+
+/// Chance in % of finding a block within `seconds` when one is expected every
+/// `block_time` seconds, 0 when none is expected
+pub fn block_chance(block_time: f64, seconds: f64) -> f64 {
+    if block_time > 0.0 {
+        (1.0 - (-seconds / block_time).exp()) * 100.0
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn erg(amount: f64) -> u64 {
+        (amount * COINS_IN_ONE_ERG as f64) as u64
+    }
+
+    #[test]
+    fn miner_reward_during_fixed_rate_period() {
+        assert_eq!(miner_reward_at_height(1), erg(67.5));
+        assert_eq!(miner_reward_at_height(525_599), erg(67.5));
+    }
+
+    #[test]
+    fn foundation_share_ends_after_two_epochs() {
+        assert_eq!(emission_at_height(600_000), erg(69.0));
+        assert_eq!(miner_reward_at_height(600_000), erg(67.5));
+        assert_eq!(miner_reward_at_height(655_200), erg(66.0));
+    }
+
+    #[test]
+    fn eip27_activation() {
+        assert_eq!(miner_reward_at_height(777_216), erg(63.0));
+        assert_eq!(miner_reward_at_height(777_217), erg(51.0));
+        assert_eq!(miner_reward_at_height(1_300_000), erg(27.0));
+    }
+
+    #[test]
+    fn reemission_pays_miners_after_emission_ends() {
+        assert_eq!(miner_reward_at_height(1_756_800), erg(3.0));
+        assert_eq!(emission_at_height(2_100_000), 0);
+        assert_eq!(miner_reward_at_height(2_100_000), erg(3.0));
+        assert_eq!(reemission_end_height(), 6_647_132);
+        assert_eq!(miner_reward_at_height(6_647_132), 0);
+    }
+
+    #[test]
+    fn next_reduction() {
+        assert_eq!(next_reduction_height(1), Some(655_200));
+        assert_eq!(next_reduction_height(700_000), Some(720_000));
+        assert_eq!(next_reduction_height(720_000), Some(777_217));
+        assert_eq!(next_reduction_height(777_217), Some(784_800));
+        assert_eq!(next_reduction_height(1_700_000), Some(1_756_800));
+        assert_eq!(next_reduction_height(1_756_800), Some(6_647_132));
+        assert_eq!(next_reduction_height(6_647_132), None);
+    }
+
+    #[test]
+    fn block_time_and_chance() {
+        let difficulty = 1.8e15;
+
+        // The whole network
+        assert_eq!(expected_block_time(difficulty, 15e12), 120.0);
+        assert!(block_chance(120.0, 3_600.0) > 99.99);
+
+        // A pool with a fifteenth of it
+        assert_eq!(expected_block_time(difficulty, 1e12), 1_800.0);
+        assert_eq!((block_chance(1_800.0, 3_600.0) * 100.0).round(), 8_647.0);
+        assert_eq!((block_chance(3_600.0, 3_600.0) * 100.0).round(), 6_321.0);
+
+        // No hashrate, no block
+        assert_eq!(expected_block_time(difficulty, 0.0), 0.0);
+        assert_eq!(block_chance(0.0, 3_600.0), 0.0);
+    }
+}
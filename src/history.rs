@@ -0,0 +1,208 @@
+//! On-disk history of the hashrate samples, so the charts survive a restart.
+//! The hashrates are stored in H/s, whatever the unit they are shown in.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use chrono::{TimeDelta, Utc};
+use rusqlite::{params, Connection};
+
+use crate::config::Config;
+
+/// Time between two prunes, the retention is counted in days
+const PRUNE_INTERVAL: Duration = Duration::from_secs(3_600);
+
+/// A hashrate sample of a series
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sample {
+    /// Unix timestamp
+    pub timestamp: i64,
+    /// Block height of the network when the sample was taken
+    pub height: u64,
+    /// H/s
+    pub value: f64,
+}
+
+/// SQLite store of the samples, shared by all the copies of the stats
+#[derive(Debug, Clone)]
+pub struct History {
+    connection: Arc<Mutex<Connection>>,
+    /// Samples older than this are deleted
+    retention: TimeDelta,
+    /// Time of the last prune
+    pruned: Arc<Mutex<Instant>>,
+}
+
+impl History {
+    pub fn open(path: &Path, retention_days: u64) -> rusqlite::Result<History> {
+        let connection = Connection::open(path)?;
+        connection.execute_batch(
+            "CREATE TABLE IF NOT EXISTS samples (
+                series TEXT NOT NULL,
+                timestamp INTEGER NOT NULL,
+                height INTEGER NOT NULL,
+                value REAL NOT NULL,
+                PRIMARY KEY (series, timestamp)
+            );
+            CREATE INDEX IF NOT EXISTS samples_timestamp ON samples (timestamp);",
+        )?;
+
+        let history = History {
+            connection: Arc::new(Mutex::new(connection)),
+            retention: TimeDelta::days(retention_days as i64),
+            pruned: Arc::new(Mutex::new(Instant::now())),
+        };
+        history.prune()?;
+
+        Ok(history)
+    }
+
+    /// Open the store in the XDG data dir, `None` if the history is disabled
+    /// or cannot be opened, the dashboard then works without it
+    pub fn open_default(config: &Config) -> Option<History> {
+        if config.history_retention_days == 0 {
+            return None;
+        }
+
+        let path = History::path()?;
+        fs::create_dir_all(path.parent()?).ok()?;
+
+        History::open(&path, config.history_retention_days).ok()
+    }
+
+    /// `$XDG_DATA_HOME/smp-tui/history.sqlite`
+    pub fn path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("smp-tui").join("history.sqlite"))
+    }
+
+    /// Store a sample, replacing the one of the series with the same timestamp
+    pub fn record(&self, series: &str, sample: &Sample) -> rusqlite::Result<()> {
+        self.connection.lock().unwrap().execute(
+            "INSERT OR REPLACE INTO samples (series, timestamp, height, value)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![series, sample.timestamp, sample.height as i64, sample.value],
+        )?;
+
+        Ok(())
+    }
+
+    /// The samples of a series taken since the `since` unix timestamp, oldest first
+    pub fn load(&self, series: &str, since: i64) -> rusqlite::Result<Vec<Sample>> {
+        let connection = self.connection.lock().unwrap();
+        let mut statement = connection.prepare(
+            "SELECT timestamp, height, value FROM samples
+             WHERE series = ?1 AND timestamp >= ?2 ORDER BY timestamp",
+        )?;
+
+        let samples = statement
+            .query_map(params![series, since], |row| {
+                Ok(Sample {
+                    timestamp: row.get(0)?,
+                    height: row.get::<_, i64>(1)? as u64,
+                    value: row.get(2)?,
+                })
+            })?
+            .collect();
+
+        samples
+    }
+
+    /// Delete the samples of a series taken since the `since` unix timestamp
+    pub fn forget(&self, series: &str, since: i64) -> rusqlite::Result<usize> {
+        self.connection.lock().unwrap().execute(
+            "DELETE FROM samples WHERE series = ?1 AND timestamp >= ?2",
+            params![series, since],
+        )
+    }
+
+    /// Delete the samples older than the retention, returns how many were deleted
+    pub fn prune(&self) -> rusqlite::Result<usize> {
+        let oldest = (Utc::now() - self.retention).timestamp();
+        *self.pruned.lock().unwrap() = Instant::now();
+
+        self.connection
+            .lock()
+            .unwrap()
+            .execute("DELETE FROM samples WHERE timestamp < ?1", params![oldest])
+    }
+
+    /// Prune the samples if the last prune is older than `PRUNE_INTERVAL`
+    pub fn prune_hourly(&self) -> rusqlite::Result<usize> {
+        if self.pruned.lock().unwrap().elapsed() < PRUNE_INTERVAL {
+            return Ok(0);
+        }
+
+        self.prune()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(timestamp: i64, value: f64) -> Sample {
+        Sample {
+            timestamp,
+            height: 1_000,
+            value,
+        }
+    }
+
+    #[test]
+    fn load_returns_the_recent_samples_oldest_first() {
+        let history = History::open(Path::new(":memory:"), 30).unwrap();
+        let now = Utc::now().timestamp();
+
+        for i in 0..5 {
+            history.record("pool", &sample(now + i, i as f64)).unwrap();
+        }
+        history.record("network", &sample(now, 42.0)).unwrap();
+        // Same timestamp, replaces the previous sample
+        history.record("pool", &sample(now + 4, 40.0)).unwrap();
+
+        let values: Vec<f64> = history
+            .load("pool", now + 2)
+            .unwrap()
+            .iter()
+            .map(|sample| sample.value)
+            .collect();
+        assert_eq!(values, vec![2.0, 3.0, 40.0]);
+    }
+
+    #[test]
+    fn forget_deletes_the_rewound_samples_of_a_series() {
+        let history = History::open(Path::new(":memory:"), 30).unwrap();
+        let now = Utc::now().timestamp();
+
+        for i in 0..3 {
+            history.record("pool", &sample(now + i, i as f64)).unwrap();
+        }
+        history.record("network", &sample(now + 2, 42.0)).unwrap();
+
+        assert_eq!(history.forget("pool", now + 1).unwrap(), 2);
+        assert_eq!(history.load("pool", 0).unwrap(), vec![sample(now, 0.0)]);
+        assert_eq!(history.load("network", 0).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn prune_deletes_samples_past_retention() {
+        let history = History::open(Path::new(":memory:"), 1).unwrap();
+        let now = Utc::now().timestamp();
+
+        history
+            .record("pool", &sample(now - 2 * 86_400, 1.0))
+            .unwrap();
+        history.record("pool", &sample(now, 2.0)).unwrap();
+
+        // Pruned when opened, not again within the hour
+        assert_eq!(history.prune_hourly().unwrap(), 0);
+        assert_eq!(history.load("pool", 0).unwrap().len(), 2);
+
+        assert_eq!(history.prune().unwrap(), 1);
+        assert_eq!(history.load("pool", 0).unwrap(), vec![sample(now, 2.0)]);
+    }
+}
@@ -1,10 +1,112 @@
-use std::io;
+use std::{io, process};
+
+use clap::Parser;
+use tokio::time::Instant;
+mod address;
+mod api;
 mod app;
+mod cli;
+mod config;
 mod data;
+mod emission;
+mod history;
+mod price;
+mod schedule;
+mod state;
+mod status;
 mod tui;
+mod units;
+mod views;
+mod worker;
 fn main() -> io::Result<()> {
+    let cli = cli::Cli::parse();
+
+    if let Some(address) = &cli.address {
+        if let Err(e) = address::validate(address) {
+            eprintln!("Invalid address {}: {}", address, e);
+            process::exit(1);
+        }
+    }
+
+    let mut config = match config::Config::load(cli.config.clone()) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
+    cli.apply(&mut config);
+
+    // The pool picked in the app, unless another one is asked for
+    if cli.pool_id.is_none() {
+        state::State::load().apply(&mut config);
+    }
+
+    if cli.once || cli.no_tui {
+        return tokio::runtime::Runtime::new()?.block_on(print_stats(&config, cli.once));
+    }
+
     let mut terminal = tui::init()?;
-    let app_result = app::App::default().run(&mut terminal);
+    let app_result = app::App::new(config).run(&mut terminal);
     tui::restore()?;
     app_result
 }
+
+/// Print the stats to stdout, once or whenever a source is refreshed on its schedule
+async fn print_stats(config: &config::Config, once: bool) -> io::Result<()> {
+    let mut stats = data::Stats::new(config);
+    if let Some(history) = history::History::open_default(config) {
+        stats.set_history(history);
+    }
+
+    if once {
+        let result = stats.get_data().await;
+        for event in stats.take_events() {
+            eprintln!("{}", event);
+        }
+
+        match result {
+            Ok(errors) => {
+                for e in errors {
+                    eprintln!("{}", e);
+                }
+                println!("{}", stats);
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+
+    let mut scheduler = schedule::Scheduler::new(config);
+    loop {
+        tokio::time::sleep_until(scheduler.next_due()).await;
+
+        let mut refreshed = false;
+        for source in scheduler.due(Instant::now()) {
+            match stats.refresh(source).await {
+                Ok(errors) => {
+                    scheduler.succeeded(source, Instant::now());
+                    refreshed = true;
+                    for e in errors {
+                        eprintln!("{}", e);
+                    }
+                }
+                Err(e) => {
+                    scheduler.failed(source, Instant::now());
+                    eprintln!("{}", e);
+                }
+            }
+        }
+
+        for event in stats.take_events() {
+            eprintln!("{}", event);
+        }
+
+        if refreshed {
+            println!("{}", stats);
+        }
+    }
+}
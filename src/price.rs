@@ -0,0 +1,468 @@
+//! Sources of the ERG price. They are tried in the configured order, the
+//! first one answering sets the price shown with its label.
+
+use std::{fmt, sync::Arc};
+
+use futures::future::BoxFuture;
+use reqwest::Client;
+
+use crate::{
+    api::{self, fetch, CoinGeckoPrices, Endpoint, ExplorerBoxes, NodeBox, SpectrumMarket},
+    config::{Config, PriceSource},
+};
+
+/// Where the ERG price comes from
+pub trait PriceProvider: fmt::Debug + Send + Sync {
+    /// Shown next to the price, e.g. "Spectrum"
+    fn label(&self) -> &str;
+
+    /// Currency of the price, e.g. "SigUSD"
+    fn currency(&self) -> &str;
+
+    /// Price of one ERG in `currency`
+    fn price<'a>(&'a self, client: &'a Client) -> BoxFuture<'a, Result<f64, api::Error>>;
+}
+
+/// The providers of the config, in order
+pub fn providers(config: &Config) -> Vec<Arc<dyn PriceProvider>> {
+    config
+        .price_sources
+        .iter()
+        .map(|source| -> Arc<dyn PriceProvider> {
+            match source {
+                PriceSource::Spectrum { url } => Arc::new(Spectrum {
+                    url: url.clone().unwrap_or_else(|| config.price_api_url.clone()),
+                }),
+                PriceSource::CoinGecko {
+                    label,
+                    url,
+                    id,
+                    currency,
+                } => Arc::new(CoinGecko {
+                    label: label.clone(),
+                    url: url.trim_end_matches('/').to_string(),
+                    id: id.clone(),
+                    currency: currency.clone(),
+                }),
+                PriceSource::Oracle { nft, node_url } => Arc::new(Oracle {
+                    nft: nft.clone(),
+                    api: match node_url {
+                        Some(url) => OracleApi::Node(url.trim_end_matches('/').to_string()),
+                        None => OracleApi::Explorer(
+                            config.explorer_url.trim_end_matches('/').to_string(),
+                        ),
+                    },
+                }),
+                PriceSource::Static { price, currency } => Arc::new(Static {
+                    price: *price,
+                    currency: currency.clone(),
+                }),
+            }
+        })
+        .collect()
+}
+
+/// Price of the first provider answering, and the provider. The error of the
+/// last one when they all fail.
+pub async fn first_price<'a>(
+    providers: &'a [Arc<dyn PriceProvider>],
+    client: &Client,
+) -> Result<(f64, &'a dyn PriceProvider), api::Error> {
+    let mut error = api::Error::Missing {
+        endpoint: Endpoint::Price,
+        field: "price source",
+    };
+
+    for provider in providers {
+        match provider.price(client).await {
+            Ok(price) => return Ok((price, provider.as_ref())),
+            Err(e) => error = e,
+        }
+    }
+
+    Err(error)
+}
+
+/// ERG/SigUSD market of the Spectrum DEX
+#[derive(Debug)]
+struct Spectrum {
+    /// `cmc/markets` endpoint
+    url: String,
+}
+
+impl PriceProvider for Spectrum {
+    fn label(&self) -> &str {
+        "Spectrum"
+    }
+
+    fn currency(&self) -> &str {
+        "SigUSD"
+    }
+
+    fn price<'a>(&'a self, client: &'a Client) -> BoxFuture<'a, Result<f64, api::Error>> {
+        Box::pin(async move {
+            let markets: Vec<SpectrumMarket> = fetch(client, Endpoint::Price, &self.url).await?;
+            spectrum_price(&markets)
+        })
+    }
+}
+
+/// The market quotes ERG in SigUSD, the price of one ERG is its inverse
+fn spectrum_price(markets: &[SpectrumMarket]) -> Result<f64, api::Error> {
+    markets
+        .iter()
+        .find(|market| market.base_name == "ERG" && market.quote_name == "SigUSD")
+        .filter(|market| market.last_price > 0.0)
+        .map(|market| 1.0 / market.last_price)
+        .ok_or(api::Error::Missing {
+            endpoint: Endpoint::Price,
+            field: "ERG/SigUSD market",
+        })
+}
+
+/// `simple/price` endpoint of CoinGecko, or of an API compatible with it
+#[derive(Debug)]
+struct CoinGecko {
+    label: String,
+    /// Base URL of the API, e.g. `https://api.coingecko.com/api/v3`
+    url: String,
+    /// Coin id of ERG, `ergo` on CoinGecko
+    id: String,
+    /// e.g. `USD`
+    currency: String,
+}
+
+impl PriceProvider for CoinGecko {
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn currency(&self) -> &str {
+        &self.currency
+    }
+
+    fn price<'a>(&'a self, client: &'a Client) -> BoxFuture<'a, Result<f64, api::Error>> {
+        Box::pin(async move {
+            let url = format!(
+                "{}/simple/price?ids={}&vs_currencies={}",
+                self.url,
+                self.id,
+                self.currency.to_lowercase()
+            );
+            let prices: CoinGeckoPrices = fetch(client, Endpoint::Price, &url).await?;
+            coingecko_price(&prices, &self.id, &self.currency)
+        })
+    }
+}
+
+fn coingecko_price(prices: &CoinGeckoPrices, id: &str, currency: &str) -> Result<f64, api::Error> {
+    prices
+        .get(id)
+        .and_then(|prices| prices.get(&currency.to_lowercase()))
+        .copied()
+        .ok_or(api::Error::Missing {
+            endpoint: Endpoint::Price,
+            field: "ERG price",
+        })
+}
+
+/// Nanoergs in one ERG
+const NANOERGS: f64 = 1e9;
+
+/// The last datapoint of an oracle pool. Its box holds the pool NFT and the
+/// nanoergs per USD in R4.
+#[derive(Debug)]
+struct Oracle {
+    nft: String,
+    api: OracleApi,
+}
+
+/// Where the boxes are looked up, by base URL
+#[derive(Debug)]
+enum OracleApi {
+    Explorer(String),
+    Node(String),
+}
+
+impl PriceProvider for Oracle {
+    fn label(&self) -> &str {
+        "Oracle pool"
+    }
+
+    fn currency(&self) -> &str {
+        "USD"
+    }
+
+    fn price<'a>(&'a self, client: &'a Client) -> BoxFuture<'a, Result<f64, api::Error>> {
+        Box::pin(async move {
+            let datapoint = match &self.api {
+                OracleApi::Explorer(url) => {
+                    let url = format!("{}/api/v1/boxes/unspent/byTokenId/{}", url, self.nft);
+                    let boxes: ExplorerBoxes = fetch(client, Endpoint::Price, &url).await?;
+                    explorer_datapoint(&boxes)
+                }
+                OracleApi::Node(url) => {
+                    let url = format!("{}/blockchain/box/unspent/byTokenId/{}", url, self.nft);
+                    let boxes: Vec<NodeBox> = fetch(client, Endpoint::Price, &url).await?;
+                    node_datapoint(&boxes)
+                }
+            };
+
+            datapoint
+                .filter(|&nanoergs| nanoergs > 0)
+                .map(|nanoergs| NANOERGS / nanoergs as f64)
+                .ok_or(api::Error::Missing {
+                    endpoint: Endpoint::Price,
+                    field: "oracle pool datapoint",
+                })
+        })
+    }
+}
+
+/// R4 of the pool box, rendered by the explorer
+fn explorer_datapoint(boxes: &ExplorerBoxes) -> Option<i64> {
+    boxes
+        .items
+        .first()?
+        .additional_registers
+        .get("R4")?
+        .rendered_value
+        .parse()
+        .ok()
+}
+
+/// R4 of the pool box, serialized by the node
+fn node_datapoint(boxes: &[NodeBox]) -> Option<i64> {
+    decode_long(boxes.first()?.additional_registers.get("R4")?)
+}
+
+/// Value of a serialized `SLong` register: the type code 0x05, then the
+/// value zigzag encoded as a VLQ
+fn decode_long(hex: &str) -> Option<i64> {
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+
+    let (&code, vlq) = bytes.split_first()?;
+    if code != 0x05 {
+        return None;
+    }
+
+    let mut value: u64 = 0;
+    for (i, byte) in vlq.iter().take(10).enumerate() {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Some((value >> 1) as i64 ^ -((value & 1) as i64));
+        }
+    }
+
+    None
+}
+
+/// A price set in the config, e.g. when every API is down
+#[derive(Debug)]
+struct Static {
+    price: f64,
+    currency: String,
+}
+
+impl PriceProvider for Static {
+    fn label(&self) -> &str {
+        "manual"
+    }
+
+    fn currency(&self) -> &str {
+        &self.currency
+    }
+
+    fn price<'a>(&'a self, _client: &'a Client) -> BoxFuture<'a, Result<f64, api::Error>> {
+        Box::pin(async move { Ok(self.price) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPECTRUM_MARKETS: &str = r#"[
+        {
+            "base_id": "0000000000000000000000000000000000000000000000000000000000000000",
+            "base_name": "ERG",
+            "quote_id": "03faf2cb329f2e90d6d23b58d91bbb6c046aa143261cc21f52fbe2824bfcbf04",
+            "quote_name": "SigUSD",
+            "last_price": 0.8,
+            "base_volume": 1520.4,
+            "quote_volume": 1216.3
+        },
+        {
+            "base_id": "0000000000000000000000000000000000000000000000000000000000000000",
+            "base_name": "ERG",
+            "quote_id": "d71693c49a84fbbecd4908c94813b46514b18b67a99952dc1e6e4791556de413",
+            "quote_name": "ergopad",
+            "last_price": 250.0,
+            "base_volume": 10.0,
+            "quote_volume": 2500.0
+        }
+    ]"#;
+
+    const EXPLORER_BOXES: &str = r#"{
+        "items": [
+            {
+                "boxId": "3fd1c2a1b4a2e4f6c0d2b8a9e7f5c3d1b9a7e5f3c1d9b7a5e3f1c9d7b5a3e1f0",
+                "value": 8000000,
+                "creationHeight": 1283245,
+                "additionalRegisters": {
+                    "R4": {
+                        "serializedValue": "0580a0f8fa05",
+                        "sigmaType": "SLong",
+                        "renderedValue": "800000000"
+                    },
+                    "R5": {
+                        "serializedValue": "04ccd09c01",
+                        "sigmaType": "SInt",
+                        "renderedValue": "1283110"
+                    }
+                }
+            }
+        ],
+        "total": 1
+    }"#;
+
+    const NODE_BOXES: &str = r#"[
+        {
+            "boxId": "3fd1c2a1b4a2e4f6c0d2b8a9e7f5c3d1b9a7e5f3c1d9b7a5e3f1c9d7b5a3e1f0",
+            "value": 8000000,
+            "creationHeight": 1283245,
+            "additionalRegisters": {
+                "R4": "0580a0f8fa05",
+                "R5": "04ccd09c01"
+            },
+            "globalIndex": 31200456
+        }
+    ]"#;
+
+    const COINGECKO_PRICES: &str = r#"{"ergo": {"usd": 1.27, "eur": 1.16}}"#;
+
+    #[test]
+    fn spectrum_inverts_the_sigusd_market() {
+        let markets: Vec<SpectrumMarket> = serde_json::from_str(SPECTRUM_MARKETS).unwrap();
+
+        assert_eq!(spectrum_price(&markets).unwrap(), 1.25);
+        assert!(spectrum_price(&markets[1..]).is_err());
+    }
+
+    #[test]
+    fn coingecko_finds_the_coin_and_currency() {
+        let prices: CoinGeckoPrices = serde_json::from_str(COINGECKO_PRICES).unwrap();
+
+        assert_eq!(coingecko_price(&prices, "ergo", "usd").unwrap(), 1.27);
+        assert_eq!(coingecko_price(&prices, "ergo", "EUR").unwrap(), 1.16);
+        assert!(coingecko_price(&prices, "ergo", "gbp").is_err());
+        assert!(coingecko_price(&prices, "bitcoin", "usd").is_err());
+    }
+
+    #[test]
+    fn oracle_datapoint_from_the_explorer_or_the_node() {
+        let explorer: ExplorerBoxes = serde_json::from_str(EXPLORER_BOXES).unwrap();
+        let node: Vec<NodeBox> = serde_json::from_str(NODE_BOXES).unwrap();
+
+        assert_eq!(explorer_datapoint(&explorer), Some(800_000_000));
+        assert_eq!(node_datapoint(&node), Some(800_000_000));
+        assert_eq!(node_datapoint(&[]), None);
+    }
+
+    #[test]
+    fn longs_are_zigzag_vlq() {
+        assert_eq!(decode_long("0500"), Some(0));
+        assert_eq!(decode_long("0505"), Some(-3));
+        assert_eq!(decode_long("0580a0f8fa05"), Some(800_000_000));
+        // An SInt, a truncated VLQ and invalid hex
+        assert_eq!(decode_long("04ccd09c01"), None);
+        assert_eq!(decode_long("0580"), None);
+        assert_eq!(decode_long("05zz"), None);
+    }
+
+    /// Always fails, like an API that is down
+    #[derive(Debug)]
+    struct Down;
+
+    impl PriceProvider for Down {
+        fn label(&self) -> &str {
+            "down"
+        }
+
+        fn currency(&self) -> &str {
+            "USD"
+        }
+
+        fn price<'a>(&'a self, _client: &'a Client) -> BoxFuture<'a, Result<f64, api::Error>> {
+            Box::pin(async move {
+                Err(api::Error::Missing {
+                    endpoint: Endpoint::Price,
+                    field: "down",
+                })
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_the_next_provider() {
+        let client = Client::new();
+        let providers: Vec<Arc<dyn PriceProvider>> = vec![
+            Arc::new(Down),
+            Arc::new(Static {
+                price: 1.5,
+                currency: "USD".to_string(),
+            }),
+        ];
+
+        let (price, provider) = first_price(&providers, &client).await.unwrap();
+        assert_eq!(price, 1.5);
+        assert_eq!(provider.label(), "manual");
+
+        assert!(first_price(&providers[..1], &client).await.is_err());
+        assert!(first_price(&[], &client).await.is_err());
+    }
+
+    #[test]
+    fn providers_follow_the_config_order() {
+        let config: Config = toml::from_str(
+            r#"
+            price_api_url = "http://spectrum.local/markets"
+            explorer_url = "http://explorer.local/"
+
+            [[price_sources]]
+            type = "oracle"
+            nft = "011d"
+
+            [[price_sources]]
+            type = "coingecko"
+
+            [[price_sources]]
+            type = "spectrum"
+
+            [[price_sources]]
+            type = "static"
+            price = 1.5
+            "#,
+        )
+        .unwrap();
+
+        let providers = providers(&config);
+        let labels: Vec<&str> = providers.iter().map(|provider| provider.label()).collect();
+        assert_eq!(
+            labels,
+            vec!["Oracle pool", "CoinGecko", "Spectrum", "manual"]
+        );
+        assert_eq!(providers[1].currency(), "USD");
+        assert_eq!(
+            format!("{:?}", providers[0]),
+            r#"Oracle { nft: "011d", api: Explorer("http://explorer.local") }"#
+        );
+        assert_eq!(
+            format!("{:?}", providers[2]),
+            r#"Spectrum { url: "http://spectrum.local/markets" }"#
+        );
+    }
+}
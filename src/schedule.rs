@@ -0,0 +1,219 @@
+//! When each source of data is refreshed. Every source has its own interval,
+//! a failing source is retried later and later, up to `max_backoff`.
+
+use std::{collections::BTreeMap, fmt, time::Duration};
+
+use tokio::time::Instant;
+
+use crate::{api::Endpoint, config::Config};
+
+/// A group of endpoints refreshed together
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Source {
+    /// Pool stats and chain height
+    Pool,
+    /// Leaderboard of the miners of the pool
+    TopMiners,
+    /// Network hashrate
+    Network,
+    Price,
+    /// Blocks found by the pool
+    Blocks,
+    /// Watched miners
+    Miners,
+    /// Past samples of the charts not seeded yet
+    Backfill,
+}
+
+impl Source {
+    /// In refresh order, the pool first as it tells the height to the others
+    pub const ALL: [Source; 7] = [
+        Source::Pool,
+        Source::TopMiners,
+        Source::Network,
+        Source::Price,
+        Source::Blocks,
+        Source::Miners,
+        Source::Backfill,
+    ];
+
+    /// Endpoints fetched when the source is refreshed
+    pub fn endpoints(self) -> &'static [Endpoint] {
+        match self {
+            Source::Pool => &[Endpoint::Pool],
+            Source::TopMiners => &[Endpoint::Miners],
+            Source::Network => &[Endpoint::NetworkInfo],
+            Source::Price => &[Endpoint::Price],
+            Source::Blocks => &[Endpoint::Blocks],
+            Source::Miners => &[
+                Endpoint::Miner,
+                Endpoint::MinerPerformance,
+                Endpoint::Payments,
+            ],
+            Source::Backfill => &[Endpoint::PoolPerformance, Endpoint::MinerPerformance],
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Source::Pool => "pool",
+            Source::TopMiners => "top miners",
+            Source::Network => "network",
+            Source::Price => "price",
+            Source::Blocks => "blocks",
+            Source::Miners => "miners",
+            Source::Backfill => "backfill",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug)]
+struct Schedule {
+    interval: Duration,
+    /// Failures in a row
+    failures: u32,
+    next: Instant,
+}
+
+/// Next refresh of every source
+#[derive(Debug)]
+pub struct Scheduler {
+    schedules: BTreeMap<Source, Schedule>,
+    max_backoff: Duration,
+}
+
+impl Scheduler {
+    /// Every source is due right away
+    pub fn new(config: &Config) -> Scheduler {
+        let now = Instant::now();
+        let schedules = Source::ALL
+            .into_iter()
+            .map(|source| {
+                let schedule = Schedule {
+                    interval: Duration::from_secs(config.interval(source).max(1)),
+                    failures: 0,
+                    next: now,
+                };
+                (source, schedule)
+            })
+            .collect();
+
+        Scheduler {
+            schedules,
+            max_backoff: Duration::from_secs(config.max_backoff),
+        }
+    }
+
+    /// Sources to refresh at `now`, in refresh order
+    pub fn due(&self, now: Instant) -> Vec<Source> {
+        self.schedules
+            .iter()
+            .filter(|(_, schedule)| schedule.next <= now)
+            .map(|(source, _)| *source)
+            .collect()
+    }
+
+    /// When the next source is due
+    pub fn next_due(&self) -> Instant {
+        self.schedules
+            .values()
+            .map(|schedule| schedule.next)
+            .min()
+            .unwrap_or_else(Instant::now)
+    }
+
+    pub fn succeeded(&mut self, source: Source, now: Instant) {
+        if let Some(schedule) = self.schedules.get_mut(&source) {
+            schedule.failures = 0;
+            schedule.next = now + schedule.interval;
+        }
+    }
+
+    /// The delay doubles on every failure in a row, but stays under
+    /// `max_backoff` unless the interval itself is longer
+    pub fn failed(&mut self, source: Source, now: Instant) {
+        if let Some(schedule) = self.schedules.get_mut(&source) {
+            schedule.failures = schedule.failures.saturating_add(1);
+            let backoff = schedule
+                .interval
+                .saturating_mul(2u32.saturating_pow(schedule.failures))
+                .min(self.max_backoff.max(schedule.interval));
+            schedule.next = now + backoff;
+        }
+    }
+
+    /// Make every source due right away, e.g. when another pool is shown
+    pub fn reset(&mut self) {
+        let now = Instant::now();
+        for schedule in self.schedules.values_mut() {
+            schedule.failures = 0;
+            schedule.next = now;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Intervals;
+
+    fn scheduler() -> Scheduler {
+        Scheduler::new(&Config {
+            intervals: Intervals {
+                price: Some(300),
+                ..Default::default()
+            },
+            max_backoff: 600,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn sources_have_their_own_interval() {
+        let mut scheduler = scheduler();
+        let now = Instant::now();
+        assert_eq!(scheduler.due(now), Source::ALL);
+
+        for source in Source::ALL {
+            scheduler.succeeded(source, now);
+        }
+        assert!(scheduler.due(now).is_empty());
+        assert_eq!(scheduler.next_due(), now + Duration::from_secs(60));
+
+        let later = now + Duration::from_secs(60);
+        assert!(!scheduler.due(later).contains(&Source::Price));
+        assert_eq!(scheduler.due(later).len(), Source::ALL.len() - 1);
+        assert_eq!(scheduler.due(now + Duration::from_secs(300)), Source::ALL);
+    }
+
+    #[test]
+    fn failures_back_off_up_to_the_maximum() {
+        let mut scheduler = scheduler();
+        let now = Instant::now();
+        let next = |scheduler: &Scheduler| scheduler.schedules[&Source::Pool].next - now;
+
+        scheduler.failed(Source::Pool, now);
+        assert_eq!(next(&scheduler), Duration::from_secs(120));
+        scheduler.failed(Source::Pool, now);
+        assert_eq!(next(&scheduler), Duration::from_secs(240));
+        for _ in 0..40 {
+            scheduler.failed(Source::Pool, now);
+        }
+        assert_eq!(next(&scheduler), Duration::from_secs(600));
+
+        scheduler.succeeded(Source::Pool, now);
+        assert_eq!(next(&scheduler), Duration::from_secs(60));
+
+        // An interval longer than the maximum backoff is kept
+        let mut scheduler = Scheduler::new(&Config {
+            refresh_interval: 900,
+            ..Default::default()
+        });
+        scheduler.failed(Source::Pool, now);
+        assert_eq!(next(&scheduler), Duration::from_secs(900));
+    }
+}
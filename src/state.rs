@@ -0,0 +1,52 @@
+use std::{fs, io, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+
+/// Choices made in the app, remembered across restarts
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct State {
+    /// Mining Core instance the pool was selected on
+    pub pool_api_url: Option<String>,
+    pub pool_id: Option<String>,
+}
+
+impl State {
+    /// `$XDG_DATA_HOME/smp-tui/state.toml`
+    pub fn path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("smp-tui").join("state.toml"))
+    }
+
+    /// Load the saved state, a missing or broken file gives the default state
+    pub fn load() -> State {
+        State::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = State::path()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No data directory"))?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let content =
+            toml::to_string(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, content)
+    }
+
+    /// Select the remembered pool, if it was picked on the configured Mining Core instance
+    pub fn apply(&self, config: &mut Config) {
+        if self.pool_api_url.as_deref() != Some(config.pool_api_url.as_str()) {
+            return;
+        }
+
+        if let Some(pool_id) = &self.pool_id {
+            config.pool_id = pool_id.clone();
+        }
+    }
+}
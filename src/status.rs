@@ -0,0 +1,59 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Local, TimeDelta};
+
+use crate::{
+    api::{self, Endpoint},
+    data::ChainEvent,
+};
+
+/// Health of the data shown on screen
+#[derive(Debug, Default)]
+pub struct Status {
+    /// Last error of every endpoint that failed since it last succeeded
+    errors: BTreeMap<Endpoint, String>,
+    /// Time of the last successful update
+    last_update: Option<DateTime<Local>>,
+    /// Last reorg or lagging API node, and when it was detected
+    last_event: Option<(DateTime<Local>, ChainEvent)>,
+}
+
+impl Status {
+    pub fn record_error(&mut self, error: &api::Error) {
+        self.errors.insert(error.endpoint(), error.to_string());
+    }
+
+    /// `endpoints` were fetched again, their errors are gone
+    pub fn record_success(&mut self, endpoints: &[Endpoint]) {
+        for endpoint in endpoints {
+            self.errors.remove(endpoint);
+        }
+        self.last_update = Some(Local::now());
+    }
+
+    pub fn record_event(&mut self, event: ChainEvent) {
+        self.last_event = Some((Local::now(), event));
+    }
+
+    /// Data is stale when the last successful update is older than `max_age`
+    pub fn is_stale(&self, max_age: TimeDelta) -> bool {
+        match self.last_update {
+            Some(last_update) => Local::now() - last_update > max_age,
+            None => true,
+        }
+    }
+
+    pub fn last_update(&self) -> Option<DateTime<Local>> {
+        self.last_update
+    }
+
+    /// Last chain event detected within `max_age`
+    pub fn recent_event(&self, max_age: TimeDelta) -> Option<(DateTime<Local>, ChainEvent)> {
+        self.last_event
+            .filter(|(time, _)| Local::now() - *time <= max_age)
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &String> {
+        self.errors.values()
+    }
+}
@@ -0,0 +1,84 @@
+//! SI prefixes for hashrates and difficulties. The stats keep raw values
+//! (H/s, difficulty) and only scale them when they are displayed.
+
+const PREFIXES: [&str; 7] = ["", "K", "M", "G", "T", "P", "E"];
+
+/// A power of 1000 and its prefix, e.g. 1e9 and "G"
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scale {
+    pub divisor: f64,
+    pub prefix: &'static str,
+}
+
+impl Scale {
+    /// The largest prefix keeping `value` at least 1 once rounded, up to E
+    pub fn of(value: f64) -> Scale {
+        let mut scale = Scale {
+            divisor: 1.0,
+            prefix: PREFIXES[0],
+        };
+
+        for next in &PREFIXES[1..] {
+            // 999_995 H/s rounds to 1000 KH/s, shown as 1 MH/s
+            if scale.apply(value).abs() < 1000.0 {
+                break;
+            }
+            scale = Scale {
+                divisor: scale.divisor * 1000.0,
+                prefix: next,
+            };
+        }
+
+        scale
+    }
+
+    /// `value` in this scale, rounded to 2 decimals
+    pub fn apply(self, value: f64) -> f64 {
+        ((value / self.divisor) * 100.0).round() / 100.0
+    }
+
+    /// Unit of a hashrate in this scale, e.g. "GH/s"
+    pub fn hashrate_unit(self) -> String {
+        format!("{}H/s", self.prefix)
+    }
+}
+
+/// e.g. "12.5 GH/s"
+pub fn format_hashrate(hashrate: f64) -> String {
+    let scale = Scale::of(hashrate);
+
+    format!("{} {}", scale.apply(hashrate), scale.hashrate_unit())
+}
+
+/// e.g. "1.73 P"
+pub fn format_difficulty(difficulty: f64) -> String {
+    let scale = Scale::of(difficulty);
+
+    format!("{} {}", scale.apply(difficulty), scale.prefix)
+        .trim_end()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashrates() {
+        assert_eq!(format_hashrate(0.0), "0 H/s");
+        assert_eq!(format_hashrate(999.0), "999 H/s");
+        assert_eq!(format_hashrate(1_000.0), "1 KH/s");
+        assert_eq!(format_hashrate(999_994.0), "999.99 KH/s");
+        assert_eq!(format_hashrate(999_995.0), "1 MH/s");
+        assert_eq!(format_hashrate(250_400_000.0), "250.4 MH/s");
+        assert_eq!(format_hashrate(12_345_000_000.0), "12.35 GH/s");
+        assert_eq!(format_hashrate(21.7e12), "21.7 TH/s");
+        assert_eq!(format_hashrate(3e21), "3000 EH/s");
+    }
+
+    #[test]
+    fn difficulties() {
+        assert_eq!(format_difficulty(512.0), "512");
+        assert_eq!(format_difficulty(1.73e15), "1.73 P");
+    }
+}
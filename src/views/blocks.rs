@@ -0,0 +1,113 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+
+use super::{shorten, Context, View};
+use crate::api::BlockStatus;
+use crate::worker::Command;
+
+/// Recent blocks found by the pool
+#[derive(Debug, Default)]
+pub struct BlocksView {
+    state: TableState,
+    rows: usize,
+}
+
+impl View for BlocksView {
+    fn render(&mut self, frame: &mut Frame, area: Rect, ctx: &Context) {
+        let header = Row::new(vec![
+            "Height",
+            "Time Found",
+            "Effort",
+            "Reward",
+            "Status",
+            "Confirmation",
+            "Finder",
+        ])
+        .style(Style::default().bold().green())
+        .bottom_margin(1);
+
+        let rows: Vec<Row> = ctx
+            .stats
+            .pool
+            .blocks
+            .iter()
+            .map(|block| {
+                let status = match block.status {
+                    BlockStatus::Pending => Cell::from("pending").yellow(),
+                    BlockStatus::Confirmed => Cell::from("confirmed").light_green(),
+                    BlockStatus::Orphaned => Cell::from("orphaned").red(),
+                };
+
+                Row::new(vec![
+                    Cell::from(block.block_height.to_string()),
+                    Cell::from(
+                        block
+                            .created
+                            .with_timezone(&chrono::Local)
+                            .format("%Y-%m-%d %H:%M")
+                            .to_string(),
+                    ),
+                    Cell::from(
+                        block
+                            .effort
+                            .map_or(String::new(), |effort| format!("{:.2} %", effort * 100.0)),
+                    ),
+                    Cell::from(format!("{:.2} Σ", block.reward)),
+                    status,
+                    Cell::from(format!("{:.0} %", block.confirmation_progress * 100.0)),
+                    Cell::from(shorten(block.miner.as_deref().unwrap_or_default())),
+                ])
+                .light_green()
+            })
+            .collect();
+
+        self.rows = rows.len();
+        if self.state.selected().is_none() && self.rows > 0 {
+            self.state.select(Some(0));
+        }
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(10),
+                Constraint::Length(18),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(11),
+                Constraint::Length(13),
+                Constraint::Min(15),
+            ],
+        )
+        .header(header)
+        .highlight_style(Style::default().reversed())
+        .block(
+            Block::bordered()
+                .title(" Pool Blocks ")
+                .border_style(Style::default().fg(Color::Green)),
+        );
+
+        frame.render_stateful_widget(table, area, &mut self.state);
+    }
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> Option<Command> {
+        if self.rows == 0 {
+            return None;
+        }
+
+        let selected = self.state.selected().unwrap_or(0);
+        let last = self.rows - 1;
+
+        let selected = match key_event.code {
+            KeyCode::Down | KeyCode::Char('j') => (selected + 1).min(last),
+            KeyCode::Up | KeyCode::Char('k') => selected.saturating_sub(1),
+            KeyCode::PageDown => (selected + 10).min(last),
+            KeyCode::PageUp => selected.saturating_sub(10),
+            KeyCode::Home | KeyCode::Char('g') => 0,
+            KeyCode::End | KeyCode::Char('G') => last,
+            _ => return None,
+        };
+
+        self.state.select(Some(selected));
+        None
+    }
+}
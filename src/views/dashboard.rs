@@ -0,0 +1,545 @@
+use chrono::{DateTime, Local, TimeDelta, Utc};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+
+use super::{miner_name, Context, View};
+use crate::{
+    data::{Resolution, TimeSeries},
+    emission,
+    units::{format_difficulty, format_hashrate, Scale},
+    worker::Command,
+};
+
+/// Span of time shown in the charts
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+enum Window {
+    Hour,
+    SixHours,
+    #[default]
+    Day,
+    Week,
+    Month,
+}
+
+impl Window {
+    const ALL: [Window; 5] = [
+        Window::Hour,
+        Window::SixHours,
+        Window::Day,
+        Window::Week,
+        Window::Month,
+    ];
+
+    fn duration(self) -> TimeDelta {
+        match self {
+            Window::Hour => TimeDelta::hours(1),
+            Window::SixHours => TimeDelta::hours(6),
+            Window::Day => TimeDelta::days(1),
+            Window::Week => TimeDelta::days(7),
+            Window::Month => TimeDelta::days(30),
+        }
+    }
+
+    /// Resolution of the charts, about a point per column at most
+    fn resolution(self) -> Resolution {
+        match self {
+            Window::Hour | Window::SixHours => Resolution::Raw,
+            Window::Day => Resolution::FiveMinutes,
+            Window::Week | Window::Month => Resolution::Hourly,
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Window::Hour => "1h",
+            Window::SixHours => "6h",
+            Window::Day => "24h",
+            Window::Week => "7d",
+            Window::Month => "30d",
+        }
+    }
+
+    fn index(self) -> usize {
+        Window::ALL
+            .iter()
+            .position(|&window| window == self)
+            .unwrap_or(0)
+    }
+
+    fn shorter(self) -> Window {
+        Window::ALL[self.index().saturating_sub(1)]
+    }
+
+    fn longer(self) -> Window {
+        Window::ALL[(self.index() + 1).min(Window::ALL.len() - 1)]
+    }
+}
+
+/// What the x axis of the charts shows
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+enum XAxis {
+    #[default]
+    Time,
+    Height,
+}
+
+/// Network, pool and miner stats with their hashrate charts
+#[derive(Debug, Default)]
+pub struct DashboardView {
+    stale: bool,
+    window: Window,
+    x_axis: XAxis,
+}
+
+impl View for DashboardView {
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> Option<Command> {
+        match key_event.code {
+            KeyCode::Char('[') => self.window = self.window.shorter(),
+            KeyCode::Char(']') => self.window = self.window.longer(),
+            KeyCode::Char('x') => {
+                self.x_axis = match self.x_axis {
+                    XAxis::Time => XAxis::Height,
+                    XAxis::Height => XAxis::Time,
+                }
+            }
+            _ => {}
+        }
+
+        None
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, ctx: &Context) {
+        let stats = ctx.stats;
+        self.stale = ctx.stale;
+
+        let stats_layout = Layout::new(
+            Direction::Vertical,
+            [
+                Constraint::Percentage(33),
+                Constraint::Percentage(33),
+                Constraint::Percentage(33),
+            ],
+        )
+        .split(area);
+
+        frame.render_widget(
+            Block::bordered()
+                .title(" Network Stats ")
+                .border_style(Style::fg(Style::default().fg(Color::Green), Color::Green)),
+            stats_layout[0],
+        );
+        frame.render_widget(
+            Block::bordered()
+                .title(" Pool Stats ".green())
+                .border_style(Style::fg(Style::default().fg(Color::Green), Color::Green)),
+            stats_layout[1],
+        );
+        let miner = ctx.miner;
+        let mut miner_title = " Miner Stats ".to_string();
+        if let Some(i) = stats.miners.iter().position(|m| m.address == miner.address) {
+            miner_title += &format!(
+                "- {} ({}/{}) ",
+                miner_name(miner),
+                i + 1,
+                stats.miners.len()
+            );
+        }
+        if let Some(rank) = stats.pool.rank(ctx.address) {
+            miner_title += &format!("- rank #{} of {} ", rank, stats.pool.connected_miners);
+        }
+
+        let x_axis = match self.x_axis {
+            XAxis::Time => "time",
+            XAxis::Height => "height",
+        };
+
+        frame.render_widget(
+            Block::bordered()
+                .title(miner_title)
+                .title_bottom(
+                    Line::from(format!(
+                        " [ ]: window {} | x: {} axis ",
+                        self.window.title(),
+                        x_axis
+                    ))
+                    .right_aligned(),
+                )
+                .border_style(Style::fg(Style::default().fg(Color::Green), Color::Green)),
+            stats_layout[2],
+        );
+
+        self.render_panel(
+            frame,
+            stats_layout[0],
+            vec![
+                " Network Hashrate ",
+                " Network Difficulty ",
+                " Block Height ",
+            ],
+            vec![" Block Reward ", " Reward Reduction in ", " ERG Price "],
+            vec![
+                format_hashrate(stats.network.current_hashrate()).as_str(),
+                format_difficulty(stats.network.difficulty).as_str(),
+                stats.network.height.to_string().as_str(),
+            ],
+            vec![
+                (stats.network.reward.to_string() + " Σ").as_str(),
+                format_countdown(stats.network.reward_reduction).as_str(),
+                format!(
+                    "{} {} ({})",
+                    stats.network.price, stats.network.price_currency, stats.network.price_source
+                )
+                .as_str(),
+            ],
+            "Network Hashrate",
+            &stats.network.hashrate,
+        );
+
+        self.render_panel(
+            frame,
+            stats_layout[1],
+            vec![" Pool Hashrate ", " Connected Miners ", " Current Effort "],
+            vec![
+                " Block found every ",
+                " Blocks found ",
+                " Confirming block ",
+            ],
+            vec![
+                format_hashrate(stats.pool.current_hashrate()).as_str(),
+                stats.pool.connected_miners.to_string().as_str(),
+                (stats.pool.effort.to_string() + " %").as_str(),
+            ],
+            vec![
+                format_block_time(stats.pool.block_time, stats.pool.block_chance).as_str(),
+                stats.pool.total_blocks.to_string().as_str(),
+                "",
+            ],
+            "Pool Hashrate",
+            &stats.pool.hashrate,
+        );
+
+        // Adding Progress Bar
+        let layout_1 = Layout::new(
+            Direction::Horizontal,
+            [Constraint::Percentage(50), Constraint::Percentage(50)],
+        )
+        .margin(1)
+        .split(stats_layout[1]);
+
+        // Split the area in 2 segments:
+        let stats_layout_progress = Layout::new(
+            Direction::Horizontal,
+            [Constraint::Percentage(50), Constraint::Percentage(50)],
+        )
+        .margin(1)
+        .split(layout_1[0]);
+
+        let stats_right = Layout::new(
+            Direction::Vertical,
+            [
+                Constraint::Percentage(33),
+                Constraint::Percentage(33),
+                Constraint::Percentage(33),
+            ],
+        )
+        .split(stats_layout_progress[1]);
+
+        frame.render_widget(
+            Gauge::default()
+                .block(Block::new().padding(Padding::proportional(1)))
+                .gauge_style(Style::default().fg(Color::LightGreen))
+                .percent(stats.pool.confirming_new_block as u16),
+            stats_right[2],
+        );
+
+        self.render_panel(
+            frame,
+            stats_layout[2],
+            vec![
+                " Current Hashrate ",
+                " Average 24h Hashrate ",
+                " Round Contribution ",
+            ],
+            vec![" Pending Shares ", " Pending Balance ", " Total Paid "],
+            vec![
+                format_hashrate(miner.current_hashrate()).as_str(),
+                format_hashrate(miner.average_hashrate).as_str(),
+                (miner.round_contribution.to_string() + " %").as_str(),
+            ],
+            vec![
+                miner.pending_shares.to_string().as_str(),
+                (miner.pending_balance.to_string() + " Σ").as_str(),
+                (miner.total_paid.to_string() + " Σ").as_str(),
+            ],
+            "Miner Hashrate",
+            &miner.hashrate,
+        );
+    }
+}
+
+impl DashboardView {
+    fn render_stats(
+        &self,
+        frame: &mut Frame,
+        area: std::rc::Rc<[Rect]>,
+        title: Vec<&str>,
+        value: Vec<&str>,
+    ) {
+        for i in 0..area.len() {
+            let block = Block::bordered()
+                .title(title[i])
+                .title_alignment(Alignment::Center)
+                .style(Style::default().green());
+
+            let mut paragraph = Paragraph::new(value[i])
+                .alignment(Alignment::Center)
+                .block(block)
+                .light_green();
+
+            if self.stale {
+                paragraph = paragraph.dim();
+            }
+
+            frame.render_widget(paragraph, area[i]);
+        }
+    }
+
+    fn render_chart<'a>(
+        &self,
+        name: &'static str,
+        y_axis_title: String,
+        summary: String,
+        style: Style,
+        data: &'a [(f64, f64)],
+    ) -> Chart<'a> {
+        // Create the datasets to fill the chart with
+        let datasets = vec![
+            // Line chart
+            Dataset::default()
+                .name(name)
+                .marker(symbols::Marker::Dot)
+                .graph_type(GraphType::Line)
+                .style(style)
+                .data(data),
+        ];
+
+        // The time axis always spans the whole window
+        let (min_value_x, max_value_x) = match self.x_axis {
+            XAxis::Time => {
+                let now = Utc::now().timestamp() as f64;
+                (now - self.window.duration().num_seconds() as f64, now)
+            }
+            XAxis::Height => (
+                data.iter()
+                    .map(|&(x, _)| x)
+                    .min_by(|a, b| a.partial_cmp(b).unwrap())
+                    .unwrap_or(0.0),
+                data.iter()
+                    .map(|&(x, _)| x)
+                    .max_by(|a, b| a.partial_cmp(b).unwrap())
+                    .unwrap_or(0.0),
+            ),
+        };
+
+        let x_axis_title = match self.x_axis {
+            XAxis::Time => format!("Time ({})", self.window.title()),
+            XAxis::Height => format!("Block ({})", self.window.title()),
+        };
+
+        // Create the X axis and define its properties
+        let x_axis = Axis::default()
+            .title(x_axis_title.green())
+            .style(Style::default().green())
+            .bounds([min_value_x, max_value_x])
+            .labels(
+                [
+                    min_value_x,
+                    ((min_value_x + max_value_x) / 2.0).round(),
+                    max_value_x,
+                ]
+                .map(|x| self.x_label(x).into())
+                .to_vec(),
+            );
+
+        let min_value_y = data
+            .iter()
+            .map(|&(_, y)| y)
+            .min_by(|a, b| a.partial_cmp(b).unwrap())
+            .unwrap_or(0.0);
+
+        let max_value_y = data
+            .iter()
+            .map(|&(_, y)| y)
+            .max_by(|a, b| a.partial_cmp(b).unwrap())
+            .unwrap_or(0.0);
+
+        // Create the Y axis and define its properties
+        let y_axis = Axis::default()
+            .title(y_axis_title.green())
+            .style(Style::default().green())
+            .bounds([
+                min_value_y - (min_value_y * 0.1),
+                max_value_y + (max_value_y * 0.1),
+            ])
+            .labels(vec![
+                ((((min_value_y - (min_value_y * 0.1)) * 100.0).round()) / 100.0)
+                    .to_string()
+                    .into(),
+                (((((min_value_y + max_value_y) / 2.0) * 100.0).round()) / 100.0)
+                    .to_string()
+                    .into(),
+                ((((max_value_y + (max_value_y * 0.1)) * 100.0).round()) / 100.0)
+                    .to_string()
+                    .into(),
+            ]);
+
+        // Create the chart and link all the parts together
+        Chart::new(datasets)
+            .block(Block::new().title(Line::from(summary.gray()).right_aligned()))
+            .x_axis(x_axis)
+            .y_axis(y_axis)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn render_panel(
+        &self,
+        frame: &mut Frame,
+        layout: Rect,
+        stats_title_left: Vec<&str>,
+        stats_title_right: Vec<&str>,
+        stats_value_left: Vec<&str>,
+        stats_value_right: Vec<&str>,
+        chart_name: &'static str,
+        chart_data: &TimeSeries,
+    ) {
+        // Points of the window, the hashrates in the prefix of the highest one
+        let oldest = (Utc::now() - self.window.duration()).timestamp();
+        let scale = Scale::of(chart_data.max(oldest).unwrap_or(0.0));
+        let summary = match (
+            chart_data.min(oldest),
+            chart_data.average(oldest),
+            chart_data.max(oldest),
+        ) {
+            (Some(min), Some(average), Some(max)) => format!(
+                "min {} | avg {} | max {} {}",
+                scale.apply(min),
+                scale.apply(average),
+                scale.apply(max),
+                scale.hashrate_unit()
+            ),
+            _ => String::new(),
+        };
+        let chart_data: Vec<(f64, f64)> = chart_data
+            .rollup(self.window.resolution(), oldest)
+            .iter()
+            .map(|sample| {
+                let x = match self.x_axis {
+                    XAxis::Time => sample.timestamp as f64,
+                    XAxis::Height => sample.height as f64,
+                };
+                (x, scale.apply(sample.value))
+            })
+            .collect();
+
+        // Rendering Stats
+        let layout_1 = Layout::new(
+            Direction::Horizontal,
+            [Constraint::Percentage(50), Constraint::Percentage(50)],
+        )
+        .margin(1)
+        .split(layout);
+
+        // Split the area in 2 segments:
+        let stats_layout = Layout::new(
+            Direction::Horizontal,
+            [Constraint::Percentage(50), Constraint::Percentage(50)],
+        )
+        .margin(1)
+        .split(layout_1[0]);
+
+        let stats_left = Layout::new(
+            Direction::Vertical,
+            [
+                Constraint::Percentage(33),
+                Constraint::Percentage(33),
+                Constraint::Percentage(33),
+            ],
+        )
+        .split(stats_layout[0]);
+
+        let stats_right = Layout::new(
+            Direction::Vertical,
+            [
+                Constraint::Percentage(33),
+                Constraint::Percentage(33),
+                Constraint::Percentage(33),
+            ],
+        )
+        .split(stats_layout[1]);
+
+        self.render_stats(frame, stats_left, stats_title_left, stats_value_left);
+
+        self.render_stats(frame, stats_right, stats_title_right, stats_value_right);
+
+        // Rendering Charts
+        frame.render_widget(
+            self.render_chart(
+                chart_name,
+                scale.hashrate_unit(),
+                summary,
+                Style::default().white(),
+                &chart_data,
+            ),
+            layout_1[1],
+        );
+    }
+}
+
+impl DashboardView {
+    /// a block height, or a local time precise enough for the window
+    fn x_label(&self, x: f64) -> String {
+        if self.x_axis == XAxis::Height {
+            return x.to_string();
+        }
+
+        let format = if self.window.duration() > TimeDelta::days(1) {
+            "%b %d"
+        } else {
+            "%H:%M"
+        };
+
+        DateTime::<Utc>::from_timestamp(x as i64, 0).map_or(String::new(), |time| {
+            time.with_timezone(&Local).format(format).to_string()
+        })
+    }
+}
+
+/// blocks left and their approximate duration, e.g. "12960 blocks (~18 days)"
+fn format_countdown(blocks: u64) -> String {
+    let hours = blocks * emission::BLOCK_TIME / 3600;
+
+    if hours >= 48 {
+        format!("{} blocks (~{} days)", blocks, hours / 24)
+    } else {
+        format!("{} blocks (~{} hours)", blocks, hours)
+    }
+}
+
+/// expected time between blocks and chance of a block in the next hour,
+/// e.g. "2h 13m (36% / 1h)"
+fn format_block_time(seconds: f64, chance: f64) -> String {
+    if seconds <= 0.0 {
+        return String::new();
+    }
+
+    let minutes = (seconds / 60.0).round() as u64;
+    let time = if minutes >= 48 * 60 {
+        format!("{}d {}h", minutes / 1440, (minutes % 1440) / 60)
+    } else if minutes >= 60 {
+        format!("{}h {}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}m", minutes)
+    };
+
+    format!("{} ({}% / 1h)", time, chance.round())
+}
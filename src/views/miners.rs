@@ -0,0 +1,182 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+
+use super::{miner_name, shorten, Context, View};
+use crate::{units::format_hashrate, worker::Command};
+
+/// Watched addresses and leaderboard of the miners of the pool
+#[derive(Debug, Default)]
+pub struct MinersView {
+    state: TableState,
+    rows: usize,
+}
+
+impl View for MinersView {
+    fn render(&mut self, frame: &mut Frame, area: Rect, ctx: &Context) {
+        let pool = &ctx.stats.pool;
+
+        let layout = Layout::new(
+            Direction::Vertical,
+            [
+                Constraint::Length(ctx.stats.miners.len() as u16 + 5),
+                Constraint::Min(0),
+            ],
+        )
+        .split(area);
+
+        render_watchlist(frame, layout[0], ctx);
+
+        let pool_hashrate = pool.current_hashrate();
+
+        let header = Row::new(vec!["Rank", "Miner", "Hashrate", "Shares/s", "Pool Share"])
+            .style(Style::default().bold().green())
+            .bottom_margin(1);
+
+        let rows: Vec<Row> = pool
+            .top_miners
+            .iter()
+            .enumerate()
+            .map(|(i, miner)| {
+                let share = if pool_hashrate > 0.0 {
+                    miner.hashrate / pool_hashrate * 100.0
+                } else {
+                    0.0
+                };
+
+                let row = Row::new(vec![
+                    format!("#{}", i + 1),
+                    miner.miner.clone(),
+                    format_hashrate(miner.hashrate),
+                    format!("{:.3}", miner.shares_per_second),
+                    format!("{:.2} %", share),
+                ]);
+
+                // Our own addresses stand out
+                if miner.miner == ctx.address || ctx.stats.miner(&miner.miner).is_some() {
+                    row.black().on_light_green()
+                } else {
+                    row.light_green()
+                }
+            })
+            .collect();
+
+        self.rows = rows.len();
+        if self.state.selected().is_none_or(|i| i >= self.rows) {
+            self.state
+                .select(if self.rows > 0 { Some(0) } else { None });
+        }
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(6),
+                Constraint::Min(20),
+                Constraint::Length(16),
+                Constraint::Length(10),
+                Constraint::Length(12),
+            ],
+        )
+        .header(header)
+        .highlight_style(Style::default().reversed())
+        .block(
+            Block::bordered()
+                .title(format!(
+                    " Top Miners - {} connected ",
+                    pool.connected_miners
+                ))
+                .border_style(Style::default().fg(Color::Green)),
+        );
+
+        frame.render_stateful_widget(table, layout[1], &mut self.state);
+    }
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> Option<Command> {
+        let selected = self.state.selected().unwrap_or(0);
+        let last = self.rows.saturating_sub(1);
+
+        match key_event.code {
+            KeyCode::Down | KeyCode::Char('j') => self.state.select(Some((selected + 1).min(last))),
+            KeyCode::Up | KeyCode::Char('k') => self.state.select(Some(selected.saturating_sub(1))),
+            KeyCode::PageDown => self.state.select(Some((selected + 10).min(last))),
+            KeyCode::PageUp => self.state.select(Some(selected.saturating_sub(10))),
+            _ => {}
+        }
+
+        None
+    }
+}
+
+/// Stats of every watched address and their sum, the shown one is marked with a *
+fn render_watchlist(frame: &mut Frame, area: Rect, ctx: &Context) {
+    let header = Row::new(vec![
+        "Label",
+        "Address",
+        "Hashrate",
+        "Pending Balance",
+        "Total Paid",
+        "Rank",
+    ])
+    .style(Style::default().bold().green())
+    .bottom_margin(1);
+
+    let mut rows: Vec<Row> = ctx
+        .stats
+        .miners
+        .iter()
+        .map(|miner| {
+            let current = if miner.address == ctx.address {
+                "* "
+            } else {
+                "  "
+            };
+
+            Row::new(vec![
+                format!("{}{}", current, miner_name(miner)),
+                shorten(&miner.address),
+                format_hashrate(miner.current_hashrate()),
+                format!("{} Σ", miner.pending_balance),
+                format!("{} Σ", miner.total_paid),
+                ctx.stats
+                    .pool
+                    .rank(&miner.address)
+                    .map_or("-".to_string(), |rank| format!("#{}", rank)),
+            ])
+            .light_green()
+        })
+        .collect();
+
+    let totals = ctx.stats.miner_totals();
+    rows.push(
+        Row::new(vec![
+            "  Total".to_string(),
+            String::new(),
+            format_hashrate(totals.hashrate),
+            format!("{} Σ", totals.pending_balance),
+            format!("{} Σ", totals.total_paid),
+            String::new(),
+        ])
+        .bold()
+        .green(),
+    );
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(20),
+            Constraint::Length(18),
+            Constraint::Length(16),
+            Constraint::Length(16),
+            Constraint::Length(14),
+            Constraint::Length(6),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::bordered()
+            .title(" Watchlist ")
+            .title_bottom(Line::from(" w: show the next address ").right_aligned())
+            .border_style(Style::default().fg(Color::Green)),
+    );
+
+    frame.render_widget(table, area);
+}
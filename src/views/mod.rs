@@ -0,0 +1,106 @@
+use crossterm::event::KeyEvent;
+use ratatui::prelude::*;
+
+use crate::{
+    config::Config,
+    data::{MinerStats, Stats},
+    worker::Command,
+};
+
+pub mod blocks;
+pub mod dashboard;
+pub mod miners;
+pub mod payments;
+pub mod settings;
+pub mod workers;
+
+/// What the views need to know about the app to draw themselves
+pub struct Context<'a> {
+    pub stats: &'a Stats,
+    pub config: &'a Config,
+    pub address: &'a str,
+    /// Stats of `address`, empty until they are fetched
+    pub miner: &'a MinerStats,
+    /// The data has not been refreshed for a while
+    pub stale: bool,
+}
+
+/// A screen of the app, selected from the tab bar
+pub trait View {
+    fn render(&mut self, frame: &mut Frame, area: Rect, ctx: &Context);
+
+    /// Handle the keys not used by the app itself, possibly asking the
+    /// fetch task for more data
+    fn handle_key_event(&mut self, _key_event: KeyEvent) -> Option<Command> {
+        None
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Tab {
+    #[default]
+    Dashboard,
+    Blocks,
+    Payments,
+    Workers,
+    Miners,
+    Settings,
+}
+
+impl Tab {
+    pub const ALL: [Tab; 6] = [
+        Tab::Dashboard,
+        Tab::Blocks,
+        Tab::Payments,
+        Tab::Workers,
+        Tab::Miners,
+        Tab::Settings,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Tab::Dashboard => "Dashboard",
+            Tab::Blocks => "Blocks",
+            Tab::Payments => "Payments",
+            Tab::Workers => "Workers",
+            Tab::Miners => "Miners",
+            Tab::Settings => "Settings",
+        }
+    }
+
+    pub fn index(self) -> usize {
+        Tab::ALL.iter().position(|&tab| tab == self).unwrap_or(0)
+    }
+
+    /// Tab of the given 1-based number key
+    pub fn from_number(number: char) -> Option<Tab> {
+        let index = number.to_digit(10)?.checked_sub(1)?;
+        Tab::ALL.get(index as usize).copied()
+    }
+
+    pub fn next(self) -> Tab {
+        Tab::ALL[(self.index() + 1) % Tab::ALL.len()]
+    }
+
+    pub fn previous(self) -> Tab {
+        Tab::ALL[(self.index() + Tab::ALL.len() - 1) % Tab::ALL.len()]
+    }
+}
+
+/// keep the beginning and the end of an address or a hash, e.g. "9fRAWh...LSV5vA"
+pub fn shorten(id: &str) -> String {
+    if id.len() > 15 && id.is_ascii() {
+        format!("{}...{}", &id[..6], &id[id.len() - 6..])
+    } else {
+        id.to_string()
+    }
+}
+
+/// label of a watched miner, or its shortened address when it has none
+pub fn miner_name(miner: &MinerStats) -> String {
+    if miner.label.is_empty() {
+        shorten(&miner.address)
+    } else {
+        miner.label.clone()
+    }
+}
@@ -0,0 +1,126 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+
+use super::{shorten, Context, View};
+use crate::{
+    data::{Confirmation, PAYMENTS_PAGE_SIZE},
+    worker::Command,
+};
+
+/// Payments received by the shown address
+#[derive(Debug, Default)]
+pub struct PaymentsView {
+    state: TableState,
+    rows: usize,
+    page: usize,
+}
+
+impl View for PaymentsView {
+    fn render(&mut self, frame: &mut Frame, area: Rect, ctx: &Context) {
+        let payments = &ctx.miner.payments;
+
+        let layout = Layout::new(
+            Direction::Vertical,
+            [Constraint::Length(3), Constraint::Min(0)],
+        )
+        .split(area);
+
+        let totals = Line::from(vec![
+            " Last 24h: ".green(),
+            format!("{} Σ", payments.total_day).light_green(),
+            "   Last 7 days: ".green(),
+            format!("{} Σ", payments.total_week).light_green(),
+            "   Last 30 days: ".green(),
+            format!("{} Σ", payments.total_month).light_green(),
+        ]);
+
+        frame.render_widget(
+            Paragraph::new(totals).alignment(Alignment::Center).block(
+                Block::bordered()
+                    .title(" Paid ")
+                    .border_style(Style::default().fg(Color::Green)),
+            ),
+            layout[0],
+        );
+
+        let header = Row::new(vec!["Date", "Amount", "Transaction", "Status"])
+            .style(Style::default().bold().green())
+            .bottom_margin(1);
+
+        let rows: Vec<Row> = payments
+            .payments
+            .iter()
+            .map(|payment| {
+                let status = match payment.confirmation {
+                    Confirmation::Confirmed(confirmations) => {
+                        Cell::from(format!("confirmed ({})", confirmations)).light_green()
+                    }
+                    Confirmation::Pending => Cell::from("pending").yellow(),
+                    Confirmation::Unknown => Cell::from("unknown").gray(),
+                };
+
+                Row::new(vec![
+                    Cell::from(
+                        payment
+                            .payment
+                            .created
+                            .with_timezone(&chrono::Local)
+                            .format("%Y-%m-%d %H:%M")
+                            .to_string(),
+                    ),
+                    Cell::from(format!("{:.2} Σ", payment.payment.amount)),
+                    Cell::from(shorten(&payment.payment.transaction_confirmation_data)),
+                    status,
+                ])
+                .light_green()
+            })
+            .collect();
+
+        self.rows = rows.len();
+        self.page = payments.page;
+        if self.state.selected().is_none_or(|i| i >= self.rows) {
+            self.state
+                .select(if self.rows > 0 { Some(0) } else { None });
+        }
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(18),
+                Constraint::Length(14),
+                Constraint::Length(18),
+                Constraint::Min(15),
+            ],
+        )
+        .header(header)
+        .highlight_style(Style::default().reversed())
+        .block(
+            Block::bordered()
+                .title(format!(" Payments - page {} ", self.page + 1))
+                .title_bottom(Line::from(" n: next page | p: previous page ").right_aligned())
+                .border_style(Style::default().fg(Color::Green)),
+        );
+
+        frame.render_stateful_widget(table, layout[1], &mut self.state);
+    }
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> Option<Command> {
+        let selected = self.state.selected().unwrap_or(0);
+        let last = self.rows.saturating_sub(1);
+
+        match key_event.code {
+            KeyCode::Down | KeyCode::Char('j') => self.state.select(Some((selected + 1).min(last))),
+            KeyCode::Up | KeyCode::Char('k') => self.state.select(Some(selected.saturating_sub(1))),
+            // Only a full page can be followed by another one
+            KeyCode::Char('n') | KeyCode::PageDown if self.rows == PAYMENTS_PAGE_SIZE => {
+                return Some(Command::PaymentsPage(self.page + 1));
+            }
+            KeyCode::Char('p') | KeyCode::PageUp if self.page > 0 => {
+                return Some(Command::PaymentsPage(self.page - 1));
+            }
+            _ => {}
+        }
+
+        None
+    }
+}
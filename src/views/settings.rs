@@ -0,0 +1,108 @@
+use ratatui::{prelude::*, widgets::*};
+
+use super::{Context, View};
+use crate::{config::Config, price, schedule::Source};
+
+const KEY_BINDINGS: [(&str, &str); 14] = [
+    ("1-6", "switch view"),
+    ("Tab / Shift-Tab", "next / previous view"),
+    ("Left / Right", "next / previous view"),
+    ("Up / Down", "scroll"),
+    ("PgUp / PgDn", "scroll a page"),
+    ("n / p", "next / previous page"),
+    ("s", "change the sort column"),
+    ("r", "reverse the sort order"),
+    ("[ / ]", "shorter / longer chart window"),
+    ("x", "charts by time / block height"),
+    ("a", "set the wallet address"),
+    ("w", "show the next watched address"),
+    ("P", "select the pool"),
+    ("q", "quit"),
+];
+
+/// Current configuration and key bindings
+#[derive(Debug, Default)]
+pub struct SettingsView;
+
+impl View for SettingsView {
+    fn render(&mut self, frame: &mut Frame, area: Rect, ctx: &Context) {
+        let layout = Layout::new(
+            Direction::Horizontal,
+            [Constraint::Percentage(60), Constraint::Percentage(40)],
+        )
+        .split(area);
+
+        let config: &Config = ctx.config;
+        let config_path = match &config.path {
+            Some(path) => path.display().to_string(),
+            None => "none, defaults used".to_string(),
+        };
+
+        let settings = vec![
+            ("Config file", config_path),
+            ("Pool API", config.pool_api_url.clone()),
+            ("Pool id", config.pool_id.clone()),
+            ("Explorer API", config.explorer_url.clone()),
+            ("Price API", config.price_api_url.clone()),
+            (
+                "Price sources",
+                price::providers(config)
+                    .iter()
+                    .map(|provider| provider.label().to_string())
+                    .collect::<Vec<_>>()
+                    .join(" > "),
+            ),
+            (
+                "Refresh intervals",
+                Source::ALL
+                    .iter()
+                    .map(|source| format!("{} {} s", source, config.interval(*source)))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ),
+            ("Max backoff", format!("{} s", config.max_backoff)),
+            (
+                "History",
+                if config.history_retention_days == 0 {
+                    "disabled".to_string()
+                } else {
+                    format!("{} days", config.history_retention_days)
+                },
+            ),
+            ("Wallet address", ctx.address.to_string()),
+            ("Watchlist", format!("{} addresses", ctx.stats.miners.len())),
+        ];
+
+        frame.render_widget(
+            Table::new(
+                settings
+                    .into_iter()
+                    .map(|(key, value)| Row::new(vec![Cell::from(key).green(), Cell::from(value)])),
+                [Constraint::Length(18), Constraint::Min(0)],
+            )
+            .light_green()
+            .block(
+                Block::bordered()
+                    .title(" Settings ")
+                    .border_style(Style::default().fg(Color::Green)),
+            ),
+            layout[0],
+        );
+
+        frame.render_widget(
+            Table::new(
+                KEY_BINDINGS.iter().map(|&(key, action)| {
+                    Row::new(vec![Cell::from(key).green(), Cell::from(action)])
+                }),
+                [Constraint::Length(18), Constraint::Min(0)],
+            )
+            .light_green()
+            .block(
+                Block::bordered()
+                    .title(" Key Bindings ")
+                    .border_style(Style::default().fg(Color::Green)),
+            ),
+            layout[1],
+        );
+    }
+}
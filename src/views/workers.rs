@@ -0,0 +1,152 @@
+use std::cmp::Ordering;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+
+use super::{Context, View};
+use crate::{data::WorkerStats, units::format_hashrate, worker::Command};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+enum SortBy {
+    #[default]
+    Name,
+    Hashrate,
+    AverageHashrate,
+    SharesPerSecond,
+    LastSeen,
+}
+
+impl SortBy {
+    fn next(self) -> SortBy {
+        match self {
+            SortBy::Name => SortBy::Hashrate,
+            SortBy::Hashrate => SortBy::AverageHashrate,
+            SortBy::AverageHashrate => SortBy::SharesPerSecond,
+            SortBy::SharesPerSecond => SortBy::LastSeen,
+            SortBy::LastSeen => SortBy::Name,
+        }
+    }
+
+    fn compare(self, a: &WorkerStats, b: &WorkerStats) -> Ordering {
+        match self {
+            SortBy::Name => a.name.cmp(&b.name),
+            SortBy::Hashrate => a.hashrate.total_cmp(&b.hashrate),
+            SortBy::AverageHashrate => a.average_hashrate.total_cmp(&b.average_hashrate),
+            SortBy::SharesPerSecond => a.shares_per_second.total_cmp(&b.shares_per_second),
+            SortBy::LastSeen => a.last_seen.cmp(&b.last_seen),
+        }
+    }
+}
+
+/// Per-worker breakdown of the shown miner
+#[derive(Debug, Default)]
+pub struct WorkersView {
+    state: TableState,
+    rows: usize,
+    sort_by: SortBy,
+    descending: bool,
+}
+
+impl View for WorkersView {
+    fn render(&mut self, frame: &mut Frame, area: Rect, ctx: &Context) {
+        let mut workers: Vec<&WorkerStats> = ctx.miner.workers.iter().collect();
+        workers.sort_by(|a, b| {
+            let ordering = self.sort_by.compare(a, b);
+            if self.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+
+        let arrow = if self.descending { " ▼" } else { " ▲" };
+        let header = Row::new(
+            [
+                (SortBy::Name, "Worker"),
+                (SortBy::Hashrate, "Hashrate"),
+                (SortBy::AverageHashrate, "Average 24h"),
+                (SortBy::SharesPerSecond, "Shares/s"),
+                (SortBy::LastSeen, "Last Seen"),
+            ]
+            .map(|(column, title)| {
+                if column == self.sort_by {
+                    title.to_string() + arrow
+                } else {
+                    title.to_string()
+                }
+            }),
+        )
+        .style(Style::default().bold().green())
+        .bottom_margin(1);
+
+        let online = workers.iter().filter(|worker| worker.online).count();
+
+        let rows: Vec<Row> = workers
+            .iter()
+            .map(|worker| {
+                let row = Row::new(vec![
+                    worker.name.clone(),
+                    format_hashrate(worker.hashrate),
+                    format_hashrate(worker.average_hashrate),
+                    format!("{:.3}", worker.shares_per_second),
+                    worker.last_seen.map_or("never".to_string(), |last_seen| {
+                        last_seen
+                            .with_timezone(&chrono::Local)
+                            .format("%Y-%m-%d %H:%M")
+                            .to_string()
+                    }),
+                ]);
+
+                if worker.online {
+                    row.light_green()
+                } else {
+                    row.red()
+                }
+            })
+            .collect();
+
+        self.rows = rows.len();
+        if self.state.selected().is_none_or(|i| i >= self.rows) {
+            self.state
+                .select(if self.rows > 0 { Some(0) } else { None });
+        }
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Min(15),
+                Constraint::Length(16),
+                Constraint::Length(16),
+                Constraint::Length(12),
+                Constraint::Length(18),
+            ],
+        )
+        .header(header)
+        .highlight_style(Style::default().reversed())
+        .block(
+            Block::bordered()
+                .title(format!(" Workers - {}/{} online ", online, self.rows))
+                .title_bottom(Line::from(" s: sort column | r: reverse order ").right_aligned())
+                .border_style(Style::default().fg(Color::Green)),
+        );
+
+        frame.render_stateful_widget(table, area, &mut self.state);
+    }
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> Option<Command> {
+        let selected = self.state.selected().unwrap_or(0);
+        let last = self.rows.saturating_sub(1);
+
+        match key_event.code {
+            KeyCode::Down | KeyCode::Char('j') => self.state.select(Some((selected + 1).min(last))),
+            KeyCode::Up | KeyCode::Char('k') => self.state.select(Some(selected.saturating_sub(1))),
+            KeyCode::PageDown => self.state.select(Some((selected + 10).min(last))),
+            KeyCode::PageUp => self.state.select(Some(selected.saturating_sub(10))),
+            KeyCode::Char('s') => self.sort_by = self.sort_by.next(),
+            KeyCode::Char('r') => self.descending = !self.descending,
+            _ => {}
+        }
+
+        None
+    }
+}
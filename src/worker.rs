@@ -0,0 +1,207 @@
+use tokio::{
+    runtime::Runtime,
+    sync::mpsc::{self, UnboundedReceiver, UnboundedSender},
+    time::{self, Instant},
+};
+
+use crate::{
+    api::{self, Endpoint, PoolSummary},
+    config::{Config, Watched},
+    data::{ChainEvent, Stats},
+    history::History,
+    schedule::{Scheduler, Source},
+};
+
+/// Requests sent from the UI to the fetch task
+#[derive(Debug)]
+pub enum Command {
+    /// Show another wallet address, added to the watchlist if needed, and
+    /// fetch its stats right away
+    SetAddress(String),
+    /// Fetch another page of the payments of the shown address
+    PaymentsPage(usize),
+    /// Fetch the pools hosted on the Mining Core instance
+    ListPools,
+    /// Show the stats of another pool of the Mining Core instance
+    SetPool(String),
+}
+
+/// Messages sent from the fetch task to the UI
+#[derive(Debug)]
+pub enum Update {
+    /// The stats, with the endpoints that were just fetched successfully
+    Stats(Box<Stats>, Vec<Endpoint>),
+    Pools(Vec<PoolSummary>),
+    Error(api::Error),
+    /// Unexpected block height reported by the pool
+    Chain(ChainEvent),
+}
+
+/// Handle to the background fetch task
+#[derive(Debug)]
+pub struct Worker {
+    pub updates: UnboundedReceiver<Update>,
+    pub commands: UnboundedSender<Command>,
+}
+
+/// Spawn the task fetching every source of the stats on its own schedule.
+/// The task stops once the `Worker` is dropped.
+pub fn spawn(runtime: &Runtime, config: &Config, address: String) -> Worker {
+    let (update_tx, update_rx) = mpsc::unbounded_channel();
+    let (command_tx, command_rx) = mpsc::unbounded_channel();
+
+    runtime.spawn(run(config.clone(), address, update_tx, command_rx));
+
+    Worker {
+        updates: update_rx,
+        commands: command_tx,
+    }
+}
+
+async fn run(
+    mut config: Config,
+    mut address: String,
+    updates: UnboundedSender<Update>,
+    mut commands: UnboundedReceiver<Command>,
+) {
+    let history = History::open_default(&config);
+    let new_stats = |config: &Config| {
+        let mut stats = Stats::new(config);
+        if let Some(history) = &history {
+            stats.set_history(history.clone());
+        }
+        stats
+    };
+    let mut stats = new_stats(&config);
+    let mut scheduler = Scheduler::new(&config);
+    let mut payments_page = 0;
+
+    loop {
+        let result = tokio::select! {
+            _ = time::sleep_until(scheduler.next_due()) => {
+                let mut fetched = Vec::new();
+                for source in scheduler.due(Instant::now()) {
+                    match refresh(&mut stats, source, &address, payments_page).await {
+                        Ok(errors) => {
+                            scheduler.succeeded(source, Instant::now());
+                            //An endpoint failing for some of the miners keeps its error
+                            fetched.extend(source.endpoints().iter().filter(|endpoint| {
+                                errors.iter().all(|e| e.endpoint() != **endpoint)
+                            }));
+                            for e in errors {
+                                if updates.send(Update::Error(e)).is_err() {
+                                    return;
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            scheduler.failed(source, Instant::now());
+                            if updates.send(Update::Error(e)).is_err() {
+                                return;
+                            }
+                        }
+                    }
+                }
+
+                for event in stats.take_events() {
+                    if updates.send(Update::Chain(event)).is_err() {
+                        return;
+                    }
+                }
+
+                if fetched.is_empty() {
+                    continue;
+                }
+                Ok(fetched)
+            }
+            command = commands.recv() => match command {
+                Some(Command::SetAddress(new_address)) => {
+                    let watched = Watched::new(&new_address);
+                    let added = stats.watch(&watched);
+                    if added {
+                        config.addresses.push(watched);
+                    }
+                    address = new_address;
+                    payments_page = 0;
+                    //A new address gets its past samples right away
+                    let result = match stats.get_miner_data(&address).await {
+                        Ok(_) if added => stats.backfill().await,
+                        result => result,
+                    };
+
+                    //The payments fail on their own
+                    let payments = stats.get_payments(&address, payments_page).await;
+                    let fetched = Source::Miners
+                        .endpoints()
+                        .iter()
+                        .copied()
+                        .filter(|endpoint| payments.is_ok() || *endpoint != Endpoint::Payments)
+                        .collect();
+                    if let Err(e) = payments {
+                        if updates.send(Update::Error(e)).is_err() {
+                            return;
+                        }
+                    }
+                    result.map(|_| fetched)
+                }
+                Some(Command::PaymentsPage(page)) if !address.is_empty() => {
+                    payments_page = page;
+                    stats
+                        .get_payments(&address, payments_page)
+                        .await
+                        .map(|_| vec![Endpoint::Payments])
+                }
+                Some(Command::PaymentsPage(_)) => continue,
+                Some(Command::SetPool(pool_id)) => {
+                    config.pool_id = pool_id;
+                    stats = new_stats(&config);
+                    payments_page = 0;
+                    //Everything is fetched again on the next turn
+                    scheduler.reset();
+                    continue;
+                }
+                Some(Command::ListPools) => {
+                    let update = match stats.get_pools().await {
+                        Ok(pools) => Update::Pools(pools),
+                        Err(e) => Update::Error(e),
+                    };
+                    if updates.send(update).is_err() {
+                        return;
+                    }
+                    continue;
+                }
+                // The UI is gone
+                None => return,
+            },
+        };
+
+        let update = match result {
+            Ok(endpoints) => Update::Stats(Box::new(stats.clone()), endpoints),
+            Err(e) => Update::Error(e),
+        };
+
+        if updates.send(update).is_err() {
+            return;
+        }
+    }
+}
+
+/// Fetch a source of the stats, the watched miners come with the payments
+/// of the shown address. Returns the errors of the miners that failed, see
+/// `Stats::refresh`, and of the payments, which do not fail the source.
+async fn refresh(
+    stats: &mut Stats,
+    source: Source,
+    address: &str,
+    payments_page: usize,
+) -> Result<Vec<api::Error>, api::Error> {
+    let mut errors = stats.refresh(source).await?;
+
+    if source == Source::Miners && !address.is_empty() {
+        if let Err(e) = stats.get_payments(address, payments_page).await {
+            errors.push(e);
+        }
+    }
+
+    Ok(errors)
+}